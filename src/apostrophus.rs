@@ -0,0 +1,243 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::{
+    parse::{parse_lenient, parse_strict, positioned_chars, push_symbols, Notation, Symbol},
+    unicode::archaic_form_values,
+    ParseMode, RomanNumeral, RomanNumeralError,
+};
+
+#[cfg(feature = "alloc")]
+const REVERSED_C: char = 'Ↄ';
+
+/// How [`RomanNumeral::to_apostrophus_string`](crate::RomanNumeral::to_apostrophus_string) writes the reversed C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ApostrophusStyle {
+    /// As U+2183, like "CIↃ" for 1000.
+    #[default]
+    Unicode,
+    /// As a closing parenthesis, like "CI)" for 1000.
+    Ascii,
+}
+
+#[cfg(feature = "alloc")]
+fn greatest_apostrophus_leq_than_n(v: i64) -> (&'static str, i64) {
+    match v {
+        100_000.. => ("CCCIↃↃↃ", 100_000),
+        90_000.. => ("CCIↃↃCCCIↃↃↃ", 90_000),
+        50_000.. => ("IↃↃↃ", 50_000),
+        40_000.. => ("CCIↃↃIↃↃↃ", 40_000),
+        10_000.. => ("CCIↃↃ", 10_000),
+        9_000.. => ("CIↃCCIↃↃ", 9_000),
+        5_000.. => ("IↃↃ", 5_000),
+        4_000.. => ("CIↃIↃↃ", 4_000),
+        1_000.. => ("CIↃ", 1_000),
+        900.. => ("CCIↃ", 900),
+        500.. => ("IↃ", 500),
+        // C IↃ would read as CIↃ
+        400.. => ("CCCC", 400),
+        100.. => ("C", 100),
+        90.. => ("XC", 90),
+        50.. => ("L", 50),
+        40.. => ("XL", 40),
+        10.. => ("X", 10),
+        9.. => ("IX", 9),
+        5.. => ("V", 5),
+        4.. => ("IV", 4),
+        1.. => ("I", 1),
+        _ => ("", 0),
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn is_c(c: char) -> bool {
+    matches!(c, 'C' | 'c' | 'Ⅽ' | 'ⅽ')
+}
+
+#[cfg(feature = "alloc")]
+fn is_i(c: char) -> bool {
+    matches!(c, 'I' | 'i' | 'Ⅰ' | 'ⅰ')
+}
+
+#[cfg(feature = "alloc")]
+fn is_reversed_c(c: char) -> bool {
+    matches!(c, REVERSED_C | 'ↄ' | ')')
+}
+
+// reads C...CIↃ...Ↄ and IↃ...Ↄ as single symbols, everything else as in plain numerals
+#[cfg(feature = "alloc")]
+fn apostrophus_symbols(s: &str) -> Result<Vec<Symbol>, RomanNumeralError> {
+    let chars: Vec<_> = positioned_chars(s).collect();
+    let run =
+        |from: usize, f: fn(char) -> bool| chars[from..].iter().take_while(|&&(_, c)| f(c)).count();
+
+    let mut symbols = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let cs = run(i, is_c);
+        let middle = i + cs;
+        if middle < chars.len() && is_i(chars[middle].1) {
+            let reversed = run(middle + 1, is_reversed_c);
+            if reversed > 3 {
+                let (pos, c) = chars[middle + 4];
+                return Err(RomanNumeralError::InvalidChar(c, pos));
+            }
+            if reversed > 0 {
+                // as many Cs as there are Ↄs make a power of ten, without any it's a five
+                let (plain, (pos, c), value) = if reversed <= cs {
+                    let start = middle - reversed;
+                    (start, chars[start], 10_i64.pow(reversed as u32 + 2))
+                } else {
+                    (middle, chars[middle], 5 * 10_i64.pow(reversed as u32 + 1))
+                };
+                for &(pos, c) in &chars[i..plain] {
+                    symbols.push(Symbol { pos, c, value: 100 });
+                }
+                symbols.push(Symbol { pos, c, value });
+                i = middle + 1 + reversed;
+                continue;
+            }
+        }
+
+        let (pos, c) = chars[i];
+        match archaic_form_values(c) {
+            Some(values) => symbols.extend(values.iter().map(|&value| Symbol { pos, c, value })),
+            None => push_symbols(&mut symbols, pos, c)?,
+        }
+        i += 1;
+    }
+
+    Ok(symbols)
+}
+
+#[cfg(feature = "alloc")]
+impl RomanNumeral {
+    /// Writes the numeral with apostrophus forms in place of D and M and for larger numbers,
+    /// up to CCCIↃↃↃ for 100000. As C IↃ would read as CIↃ, 400 is written CCCC.
+    ///
+    /// ```
+    /// use roman_numerals::{ApostrophusStyle, RomanNumeral};
+    ///
+    /// let n = RomanNumeral::with_value(1_500).unwrap();
+    /// assert_eq!(n.to_apostrophus_string(ApostrophusStyle::Unicode), "CIↃIↃ");
+    /// assert_eq!(n.to_apostrophus_string(ApostrophusStyle::Ascii), "CI)I)");
+    /// ```
+    pub fn to_apostrophus_string(&self, style: ApostrophusStyle) -> String {
+        let mut result = String::new();
+        let mut val = self.value();
+
+        while val > 0 {
+            let (s, v) = greatest_apostrophus_leq_than_n(val);
+            val -= v;
+            match style {
+                ApostrophusStyle::Unicode => result.push_str(s),
+                ApostrophusStyle::Ascii => result.extend(s.chars().map(|c| match c {
+                    REVERSED_C => ')',
+                    c => c,
+                })),
+            }
+        }
+
+        result
+    }
+
+    /// Reads a numeral written with apostrophus forms, either spelled out with U+2183, U+2184
+    /// or ')', or as the precomposed ↀ, ↁ, ↂ, ↇ and ↈ. D and M are accepted as well, and so
+    /// are the early forms ↅ for six and ↆ for fifty, which [`RomanNumeral::parse`] doesn't
+    /// read.
+    ///
+    /// [`ParseMode::Strict`] follows the spellings
+    /// [`RomanNumeral::to_apostrophus_string`] writes, so 400 has to be CCCC rather than CD.
+    ///
+    /// ```
+    /// use roman_numerals::{ParseMode, RomanNumeral};
+    ///
+    /// let n = RomanNumeral::from_apostrophus_str("CCIↃↃIↃↃ", ParseMode::Strict).unwrap();
+    /// assert_eq!(n.to_int(), 15_000);
+    /// let n = RomanNumeral::from_apostrophus_str("ↀↀCI)", ParseMode::Lenient).unwrap();
+    /// assert_eq!(n.to_int(), 3_000);
+    /// ```
+    pub fn from_apostrophus_str(
+        s: &str,
+        mode: ParseMode,
+    ) -> Result<RomanNumeral, RomanNumeralError> {
+        let symbols = apostrophus_symbols(s)?;
+        let value = match mode {
+            ParseMode::Strict => parse_strict(&symbols, Notation::Apostrophus)?,
+            ParseMode::Lenient => parse_lenient(symbols.into_iter().map(Ok))?,
+        };
+        RomanNumeral::from_i64(value)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{ApostrophusStyle, ParseMode, Position, RomanNumeral, RomanNumeralError};
+
+    fn strict(s: &str) -> u32 {
+        RomanNumeral::from_apostrophus_str(s, ParseMode::Strict)
+            .unwrap()
+            .to_int()
+    }
+
+    #[test]
+    fn classical_forms() {
+        assert_eq!(strict("IↃ"), 500);
+        assert_eq!(strict("CIↃ"), 1_000);
+        assert_eq!(strict("ↀ"), 1_000);
+        assert_eq!(strict("IↃↃ"), 5_000);
+        assert_eq!(strict("ↁ"), 5_000);
+        assert_eq!(strict("CCIↃↃ"), 10_000);
+        assert_eq!(strict("ↂ"), 10_000);
+        assert_eq!(strict("IↃↃↃ"), 50_000);
+        assert_eq!(strict("CCCIↃↃↃ"), 100_000);
+        assert_eq!(strict("ccciↄↄↄ"), 100_000);
+        assert_eq!(strict("CI)"), 1_000);
+        assert_eq!(strict("CIↃCCCCLXXXIV"), 1_484);
+        assert_eq!(strict("MD"), 1_500);
+        assert_eq!(strict("ↈↇↂↁↀ"), 166_000);
+        assert_eq!(strict("ↆↅ"), 56);
+    }
+
+    #[test]
+    fn convert_back_and_forth() {
+        for i in (1..20_000).chain((20_000..500_000).step_by(7)) {
+            for style in [ApostrophusStyle::Unicode, ApostrophusStyle::Ascii] {
+                let string = RomanNumeral::with_value(i)
+                    .unwrap()
+                    .to_apostrophus_string(style);
+                assert_eq!(
+                    i,
+                    strict(&string),
+                    "result of to_apostrophus_string(): {string:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            RomanNumeral::from_apostrophus_str("CIↃↃↃↃ", ParseMode::Strict).unwrap_err(),
+            RomanNumeralError::InvalidChar('Ↄ', Position { byte: 11, char: 5 })
+        );
+        for s in [
+            "Ↄ",
+            "CↃ",
+            "IↃIↃ",
+            "CCCCC",
+            "IↃCCCC",
+            "",
+            "ↀↀↀↀↁ",
+            "ↅI",
+            "CD",
+            "CIↃCD",
+        ] {
+            assert!(
+                RomanNumeral::from_apostrophus_str(s, ParseMode::Strict).is_err(),
+                "expected {s:?} to be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use core::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign},
+};
+
+use crate::{RomanNumeral, RomanNumeralError};
+
+impl RomanNumeral {
+    // applies `op` to the values of two numerals, making sure the result has a numeral
+    fn checked_op(
+        self,
+        rhs: RomanNumeral,
+        op: fn(i64, i64) -> Option<i64>,
+    ) -> Result<RomanNumeral, RomanNumeralError> {
+        match op(self.value(), rhs.value()) {
+            Some(value) => RomanNumeral::from_i64(value),
+            None => Err(RomanNumeralError::Overflow),
+        }
+    }
+
+    /// Adds two numerals, failing with [`RomanNumeralError::Overflow`] if the sum is too
+    /// large.
+    ///
+    /// ```
+    /// use roman_numerals::{roman, RomanNumeral};
+    ///
+    /// let sum = RomanNumeral::with_value(roman!(MCM))?.checked_add(RomanNumeral::with_value(roman!(XL))?);
+    /// assert_eq!(sum?.to_int(), roman!(MCMXL));
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub fn checked_add(self, rhs: RomanNumeral) -> Result<RomanNumeral, RomanNumeralError> {
+        self.checked_op(rhs, i64::checked_add)
+    }
+
+    /// Subtracts `rhs`, failing with [`RomanNumeralError::NonPositiveNumeral`] unless it's
+    /// smaller than `self`.
+    ///
+    /// ```
+    /// use roman_numerals::{RomanNumeral, RomanNumeralError};
+    ///
+    /// let x = RomanNumeral::with_value(10)?;
+    /// let err = x.checked_sub(RomanNumeral::with_value(10)?).unwrap_err();
+    /// assert_eq!(err, RomanNumeralError::NonPositiveNumeral);
+    /// # Ok::<(), RomanNumeralError>(())
+    /// ```
+    pub fn checked_sub(self, rhs: RomanNumeral) -> Result<RomanNumeral, RomanNumeralError> {
+        self.checked_op(rhs, i64::checked_sub)
+    }
+
+    /// Multiplies two numerals, failing with [`RomanNumeralError::Overflow`] if the product is
+    /// too large.
+    pub fn checked_mul(self, rhs: RomanNumeral) -> Result<RomanNumeral, RomanNumeralError> {
+        self.checked_op(rhs, i64::checked_mul)
+    }
+
+    /// Divides by `rhs` rounding down, failing with [`RomanNumeralError::NonPositiveNumeral`]
+    /// if `rhs` is larger than `self`.
+    pub fn checked_div(self, rhs: RomanNumeral) -> Result<RomanNumeral, RomanNumeralError> {
+        self.checked_op(rhs, i64::checked_div)
+    }
+
+    /// The remainder of dividing by `rhs`, failing with
+    /// [`RomanNumeralError::NonPositiveNumeral`] if `rhs` divides `self` evenly.
+    ///
+    /// There's no `saturating_rem`, as clamping an even division's remainder to I would
+    /// claim there's one left over.
+    pub fn checked_rem(self, rhs: RomanNumeral) -> Result<RomanNumeral, RomanNumeralError> {
+        self.checked_op(rhs, i64::checked_rem)
+    }
+
+    // like `checked_op`, but results outside of 1 to u32::MAX are clamped to it
+    fn saturating_op(self, rhs: RomanNumeral, op: fn(i64, i64) -> i64) -> RomanNumeral {
+        let value = op(self.value(), rhs.value()).clamp(1, u32::MAX.into());
+        RomanNumeral::from_i64(value).expect("the value was clamped to the numeral range")
+    }
+
+    /// Adds two numerals, stopping at the largest value instead of overflowing.
+    pub fn saturating_add(self, rhs: RomanNumeral) -> RomanNumeral {
+        self.saturating_op(rhs, i64::saturating_add)
+    }
+
+    /// Subtracts `rhs`, stopping at I instead of going to zero or below.
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let v = RomanNumeral::with_value(5)?;
+    /// assert_eq!(v.saturating_sub(RomanNumeral::with_value(10)?).to_int(), 1);
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub fn saturating_sub(self, rhs: RomanNumeral) -> RomanNumeral {
+        self.saturating_op(rhs, i64::saturating_sub)
+    }
+
+    /// Multiplies two numerals, stopping at the largest value instead of overflowing.
+    pub fn saturating_mul(self, rhs: RomanNumeral) -> RomanNumeral {
+        self.saturating_op(rhs, i64::saturating_mul)
+    }
+
+    /// Divides by `rhs` rounding down, stopping at I instead of going to zero.
+    pub fn saturating_div(self, rhs: RomanNumeral) -> RomanNumeral {
+        self.saturating_op(rhs, i64::saturating_div)
+    }
+}
+
+// the operators panic where the checked methods fail, just like integer overflow would
+macro_rules! impl_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident, $checked:ident, $verb:literal) => {
+        impl $op for RomanNumeral {
+            type Output = RomanNumeral;
+            fn $method(self, rhs: RomanNumeral) -> RomanNumeral {
+                match self.$checked(rhs) {
+                    Ok(result) => result,
+                    Err(e) => panic!(concat!("cannot ", $verb, " roman numerals: {}"), e),
+                }
+            }
+        }
+
+        impl $assign for RomanNumeral {
+            fn $assign_method(&mut self, rhs: RomanNumeral) {
+                *self = $op::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, AddAssign, add_assign, checked_add, "add");
+impl_op!(Sub, sub, SubAssign, sub_assign, checked_sub, "subtract");
+impl_op!(Mul, mul, MulAssign, mul_assign, checked_mul, "multiply");
+impl_op!(Div, div, DivAssign, div_assign, checked_div, "divide");
+impl_op!(
+    Rem,
+    rem,
+    RemAssign,
+    rem_assign,
+    checked_rem,
+    "take the remainder of"
+);
+
+impl Sum for RomanNumeral {
+    /// Adds up the numerals like [`Add`].
+    ///
+    /// # Panics
+    ///
+    /// If the iterator is empty, since the sum of no numerals would be zero, which has no
+    /// numeral, or if the sum overflows. Summing into an `Option<RomanNumeral>` gives `None`
+    /// for no numerals instead.
+    fn sum<I: Iterator<Item = RomanNumeral>>(iter: I) -> RomanNumeral {
+        iter.reduce(Add::add)
+            .expect("cannot sum an empty iterator of roman numerals")
+    }
+}
+
+impl Product for RomanNumeral {
+    /// Multiplies the numerals like [`Mul`]. The product of no numerals is I.
+    ///
+    /// # Panics
+    ///
+    /// If the product overflows.
+    fn product<I: Iterator<Item = RomanNumeral>>(iter: I) -> RomanNumeral {
+        iter.fold(RomanNumeral::MIN, Mul::mul)
+    }
+}
+
+impl<'a> Sum<&'a RomanNumeral> for RomanNumeral {
+    /// Adds up the numerals like [`Add`].
+    ///
+    /// # Panics
+    ///
+    /// If the iterator is empty, since the sum of no numerals would be zero, which has no
+    /// numeral, or if the sum overflows. Summing into an `Option<RomanNumeral>` gives `None`
+    /// for no numerals instead.
+    fn sum<I: Iterator<Item = &'a RomanNumeral>>(iter: I) -> RomanNumeral {
+        iter.copied().sum()
+    }
+}
+
+impl Sum<RomanNumeral> for Option<RomanNumeral> {
+    /// Adds up the numerals like [`Add`], or `None` if there are none.
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let none: Option<RomanNumeral> = core::iter::empty::<RomanNumeral>().sum();
+    /// assert_eq!(none, None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the sum overflows.
+    fn sum<I: Iterator<Item = RomanNumeral>>(iter: I) -> Option<RomanNumeral> {
+        iter.reduce(Add::add)
+    }
+}
+
+impl<'a> Sum<&'a RomanNumeral> for Option<RomanNumeral> {
+    /// Adds up the numerals like [`Add`], or `None` if there are none.
+    ///
+    /// # Panics
+    ///
+    /// If the sum overflows.
+    fn sum<I: Iterator<Item = &'a RomanNumeral>>(iter: I) -> Option<RomanNumeral> {
+        iter.copied().sum()
+    }
+}
+
+impl<'a> Product<&'a RomanNumeral> for RomanNumeral {
+    /// Multiplies the numerals like [`Mul`]. The product of no numerals is I.
+    ///
+    /// # Panics
+    ///
+    /// If the product overflows.
+    fn product<I: Iterator<Item = &'a RomanNumeral>>(iter: I) -> RomanNumeral {
+        iter.copied().product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use crate::{RomanNumeral, RomanNumeralError};
+
+    fn n(v: u32) -> RomanNumeral {
+        RomanNumeral::with_value(v).unwrap()
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!((n(1900) + n(40)).to_int(), 1940);
+        assert_eq!((n(2023) - n(1984)).to_int(), 39);
+        assert_eq!((n(12) * n(12)).to_int(), 144);
+        assert_eq!((n(100) / n(9)).to_int(), 11);
+        assert_eq!((n(100) % n(9)).to_int(), 1);
+
+        let mut v = n(10);
+        v += n(5);
+        v *= n(3);
+        v -= n(4);
+        v /= n(2);
+        v %= n(6);
+        assert_eq!(v.to_int(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn subtracting_to_zero() {
+        let _ = n(5) - n(5);
+    }
+
+    #[test]
+    #[should_panic = "cannot sum an empty iterator of roman numerals"]
+    fn summing_nothing() {
+        let _: RomanNumeral = std::iter::empty::<RomanNumeral>().sum();
+    }
+
+    #[test]
+    fn checked() {
+        assert_eq!(
+            n(5).checked_sub(n(6)).unwrap_err(),
+            RomanNumeralError::NonPositiveNumeral
+        );
+        assert_eq!(
+            n(u32::MAX).checked_add(n(1)).unwrap_err(),
+            RomanNumeralError::Overflow
+        );
+        assert_eq!(
+            n(3).checked_div(n(10)).unwrap_err(),
+            RomanNumeralError::NonPositiveNumeral
+        );
+        assert_eq!(
+            n(10).checked_rem(n(5)).unwrap_err(),
+            RomanNumeralError::NonPositiveNumeral
+        );
+    }
+
+    #[test]
+    fn saturating() {
+        assert_eq!(n(5).saturating_sub(n(6)).to_int(), 1);
+        assert_eq!(n(u32::MAX).saturating_add(n(1)).to_int(), u32::MAX);
+        assert_eq!(n(u32::MAX).saturating_mul(n(2)).to_int(), u32::MAX);
+        assert_eq!(n(3).saturating_div(n(10)).to_int(), 1);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let sum: RomanNumeral = (1..=10).map(n).sum();
+        assert_eq!(sum.to_int(), 55);
+        let product: RomanNumeral = (1..=5).map(n).product();
+        assert_eq!(product.to_int(), 120);
+        let empty: RomanNumeral = std::iter::empty::<RomanNumeral>().product();
+        assert_eq!(empty.to_int(), 1);
+
+        let numerals: Vec<_> = [10, 20, 30].into_iter().map(n).collect();
+        assert_eq!(numerals.iter().sum::<RomanNumeral>().to_int(), 60);
+        assert_eq!(numerals.iter().product::<RomanNumeral>().to_int(), 6000);
+
+        let sum: Option<RomanNumeral> = numerals.iter().sum();
+        assert_eq!(sum, Some(n(60)));
+        assert_eq!(
+            numerals.into_iter().skip(3).sum::<Option<RomanNumeral>>(),
+            None
+        );
+    }
+}
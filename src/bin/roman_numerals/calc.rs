@@ -0,0 +1,420 @@
+use std::fmt;
+
+use roman_numerals::{FormatOptions, ParseMode, RomanFraction, RomanNumeral, RomanNumeralError};
+
+// how to show what's left over after a division
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Remainders {
+    // XXX remainder I
+    Classical,
+    // in twelfths, the way the Romans wrote fractions, with S for a half and a dot for
+    // each twelfth, like XXX···· for 30 and 4/12
+    Twelfths,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Op {
+    fn symbol(self) -> char {
+        match self {
+            Op::Add => '+',
+            Op::Sub => '-',
+            Op::Mul => '*',
+            Op::Div => '/',
+        }
+    }
+
+    fn apply(
+        self,
+        lhs: RomanNumeral,
+        rhs: RomanNumeral,
+    ) -> Result<RomanNumeral, RomanNumeralError> {
+        match self {
+            Op::Add => lhs.checked_add(rhs),
+            Op::Sub => lhs.checked_sub(rhs),
+            Op::Mul => lhs.checked_mul(rhs),
+            Op::Div => lhs.checked_div(rhs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Number(RomanNumeral),
+    Op(Op),
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Number(RomanNumeral),
+    // the column of the operator comes along for errors
+    Binary(Box<Expr>, Op, usize, Box<Expr>),
+}
+
+// what went wrong, and the column (counting chars from 1) where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CalcError {
+    column: usize,
+    message: String,
+}
+
+impl CalcError {
+    fn at(column: usize, message: impl Into<String>) -> CalcError {
+        CalcError {
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at column {}", self.message, self.column)
+    }
+}
+
+// whether `input` is more than a single numeral or integer, so it's worth evaluating
+pub(crate) fn is_expression(input: &str) -> bool {
+    let is_operator = |c| matches!(c, '+' | '-' | '*' | '/' | '×' | '÷' | '(' | ')');
+    // a leading minus is a negative integer, which gets a better error converted alone
+    input.starts_with('(') || input.chars().skip(1).any(is_operator)
+}
+
+// numerals, integers, operators and parentheses, along with their columns
+fn tokens(input: &str, mode: ParseMode) -> Result<Vec<(usize, Token)>, CalcError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().enumerate().peekable();
+    while let Some((i, (start, c))) = chars.next() {
+        let column = i + 1;
+        let token = match c {
+            '+' => Token::Op(Op::Add),
+            '-' | '−' => Token::Op(Op::Sub),
+            '*' | '×' => Token::Op(Op::Mul),
+            '/' | '÷' => Token::Op(Op::Div),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            c if c.is_whitespace() => continue,
+            c if c.is_ascii_digit() || c.is_alphabetic() => {
+                let digits = c.is_ascii_digit();
+                let mut end = start + c.len_utf8();
+                while let Some(&(_, (i, c))) = chars.peek() {
+                    if digits != c.is_ascii_digit() || !c.is_alphanumeric() {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let word = &input[start..end];
+                let numeral = if digits {
+                    word.parse::<u64>()
+                        .map_err(|_| RomanNumeralError::Overflow)
+                        .and_then(RomanNumeral::try_from)
+                } else {
+                    RomanNumeral::parse(word, mode)
+                };
+                Token::Number(
+                    numeral.map_err(|err| CalcError::at(column, format!("{word:?}: {err}")))?,
+                )
+            }
+            c => return Err(CalcError::at(column, format!("unexpected {c:?}"))),
+        };
+        tokens.push((column, token));
+    }
+    Ok(tokens)
+}
+
+// recursive descent over the tokens, with * and / binding tighter than + and -
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    // the column just past the input, for errors at the end
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<(usize, Token)> {
+        self.tokens.get(self.next).copied()
+    }
+
+    fn binary(
+        &mut self,
+        ops: &[Op],
+        operand: fn(&mut Parser) -> Result<Expr, CalcError>,
+    ) -> Result<Expr, CalcError> {
+        let mut lhs = operand(self)?;
+        while let Some((column, Token::Op(op))) = self.peek() {
+            if !ops.contains(&op) {
+                break;
+            }
+            self.next += 1;
+            let rhs = operand(self)?;
+            lhs = Expr::Binary(Box::new(lhs), op, column, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn expr(&mut self) -> Result<Expr, CalcError> {
+        self.binary(&[Op::Add, Op::Sub], Parser::term)
+    }
+
+    fn term(&mut self) -> Result<Expr, CalcError> {
+        self.binary(&[Op::Mul, Op::Div], Parser::factor)
+    }
+
+    fn factor(&mut self) -> Result<Expr, CalcError> {
+        let Some((column, token)) = self.peek() else {
+            return Err(CalcError::at(self.end, "expected a number"));
+        };
+        self.next += 1;
+        match token {
+            Token::Number(n) => Ok(Expr::Number(n)),
+            Token::Open => {
+                let expr = self.expr()?;
+                match self.peek() {
+                    Some((_, Token::Close)) => {
+                        self.next += 1;
+                        Ok(expr)
+                    }
+                    Some((column, _)) => Err(CalcError::at(column, "expected ')'")),
+                    None => Err(CalcError::at(self.end, "expected ')'")),
+                }
+            }
+            Token::Op(op) => Err(CalcError::at(
+                column,
+                format!("expected a number before '{}'", op.symbol()),
+            )),
+            Token::Close => Err(CalcError::at(column, "expected a number before ')'")),
+        }
+    }
+}
+
+fn parse(input: &str, mode: ParseMode) -> Result<Expr, CalcError> {
+    let mut parser = Parser {
+        tokens: tokens(input, mode)?,
+        next: 0,
+        end: input.chars().count() + 1,
+    };
+    let expr = parser.expr()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some((column, Token::Close)) => Err(CalcError::at(column, "unmatched ')'")),
+        Some((column, _)) => Err(CalcError::at(column, "expected an operator")),
+    }
+}
+
+fn eval(expr: &Expr) -> Result<RomanNumeral, CalcError> {
+    match expr {
+        &Expr::Number(n) => Ok(n),
+        Expr::Binary(lhs, op, column, rhs) => {
+            let (lhs, rhs) = (eval(lhs)?, eval(rhs)?);
+            // only the division at the top has somewhere to show what's left over
+            if *op == Op::Div && lhs.checked_rem(rhs).is_ok() {
+                let message = format!(
+                    "{lhs} / {rhs} leaves a remainder, which only the last division can show"
+                );
+                return Err(CalcError::at(*column, message));
+            }
+            op.apply(lhs, rhs).map_err(|err| {
+                CalcError::at(*column, format!("{lhs} {} {rhs}: {err}", op.symbol()))
+            })
+        }
+    }
+}
+
+// the result of an expression, with what's left over if it ends in a division
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Answer {
+    // none when a division comes to less than one, which only twelfths can show
+    whole: Option<RomanNumeral>,
+    // the remainder and the divisor
+    left_over: Option<(RomanNumeral, RomanNumeral)>,
+}
+
+impl Answer {
+    // the answer in both notations, like "XXX remainder I (30 remainder 1)"
+    pub(crate) fn show(&self, options: FormatOptions, remainders: Remainders) -> String {
+        let value = self.whole.map_or(0, |whole| whole.to_int());
+        let whole = self
+            .whole
+            .map(|whole| whole.display_with(options).to_string())
+            .unwrap_or_default();
+        let Some((rem, divisor)) = self.left_over else {
+            return format!("{whole} ({value})");
+        };
+        match remainders {
+            Remainders::Classical => format!(
+                "{whole} remainder {} ({value} remainder {})",
+                rem.display_with(options),
+                rem.to_int()
+            ),
+            Remainders::Twelfths => {
+                let n = twelfths(rem, divisor);
+                let about = if u64::from(rem.to_int()) * 12 % u64::from(divisor.to_int()) == 0 {
+                    ""
+                } else {
+                    "about "
+                };
+                // the whole part keeps the bars for thousands, which a fraction doesn't have
+                let fraction = match RomanFraction::new(0, n) {
+                    Ok(fraction) if options.is_lowercase() => format!("{fraction:#}"),
+                    Ok(fraction) => fraction.to_string(),
+                    Err(_) => String::new(),
+                };
+                format!("{about}{whole}{fraction} ({value} {n}/12)")
+            }
+        }
+    }
+}
+
+// how many whole twelfths of the divisor the remainder comes to
+fn twelfths(rem: RomanNumeral, divisor: RomanNumeral) -> u32 {
+    let n = u64::from(rem.to_int()) * 12 / u64::from(divisor.to_int());
+    u32::try_from(n).expect("a remainder is less than its divisor")
+}
+
+// evaluates an expression of numerals and integers, with remainders kept for a division
+// at the top, like the one in `(C - IX) / III`, while any inside has to come out even
+pub(crate) fn evaluate(
+    input: &str,
+    mode: ParseMode,
+    remainders: Remainders,
+) -> Result<Answer, CalcError> {
+    match parse(input, mode)? {
+        Expr::Binary(lhs, Op::Div, column, rhs) => {
+            let (lhs, rhs) = (eval(&lhs)?, eval(&rhs)?);
+            // an even division has no remainder to speak of
+            let left_over = lhs.checked_rem(rhs).ok().map(|rem| (rem, rhs));
+            let whole = match lhs.checked_div(rhs) {
+                Ok(whole) => Some(whole),
+                // less than one is only a fraction in twelfths, as long as there's a twelfth
+                Err(_)
+                    if remainders == Remainders::Twelfths
+                        && left_over.is_some_and(|(rem, rhs)| twelfths(rem, rhs) > 0) =>
+                {
+                    None
+                }
+                Err(err) => {
+                    return Err(CalcError::at(column, format!("{lhs} / {rhs}: {err}")));
+                }
+            };
+            Ok(Answer { whole, left_over })
+        }
+        expr => Ok(Answer {
+            whole: Some(eval(&expr)?),
+            left_over: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roman_numerals::{FormatOptions, ParseMode};
+
+    use super::{evaluate, is_expression, Remainders};
+
+    fn calc(input: &str, remainders: Remainders) -> String {
+        match evaluate(input, ParseMode::Strict, remainders) {
+            Ok(answer) => answer.show(FormatOptions::new(), remainders),
+            Err(err) => format!("error: {err}"),
+        }
+    }
+
+    #[test]
+    fn evaluates() {
+        let classical = |input| calc(input, Remainders::Classical);
+        assert_eq!(classical("MCM + XL * II"), "MCMLXXX (1980)");
+        assert_eq!(classical("(MCM + XL) * II"), "MMMDCCCLXXX (3880)");
+        assert_eq!(classical("MMXXIII - 1984"), "XXXIX (39)");
+        assert_eq!(classical("x*x-i"), "XCIX (99)");
+        assert_eq!(
+            classical("(C - IX) / III"),
+            "XXX remainder I (30 remainder 1)"
+        );
+        assert_eq!(classical("XC / III"), "XXX (30)");
+        assert_eq!(
+            classical("XI / XII"),
+            "error: XI / XII: roman numerals can only represent positive numbers at column 4"
+        );
+        assert_eq!(classical("C / V * II"), "XL (40)");
+        assert_eq!(classical("((IV))"), "IV (4)");
+    }
+
+    #[test]
+    fn twelfths() {
+        let twelfths = |input| calc(input, Remainders::Twelfths);
+        assert_eq!(twelfths("(C - IX) / III"), "XXX···· (30 4/12)");
+        assert_eq!(twelfths("XV / II"), "VIIS (7 6/12)");
+        assert_eq!(twelfths("XI / XII"), "S····· (0 11/12)");
+        assert_eq!(twelfths("I / III"), "···· (0 4/12)");
+        assert_eq!(
+            twelfths("I / XIII"),
+            "error: I / XIII: roman numerals can only represent positive numbers at column 3"
+        );
+        assert_eq!(twelfths("XXIII / XII"), "IS····· (1 11/12)");
+        assert_eq!(twelfths("X / VII"), "about I····· (1 5/12)");
+        assert_eq!(twelfths("XII / VI"), "II (2)");
+    }
+
+    #[test]
+    fn modes() {
+        let answer = evaluate("iiii * M", ParseMode::Lenient, Remainders::Classical).unwrap();
+        assert_eq!(
+            answer.show(FormatOptions::new().lowercase(true), Remainders::Classical),
+            "mmmm (4000)"
+        );
+        assert!(evaluate("IIII * M", ParseMode::Strict, Remainders::Classical).is_err());
+        let answer = evaluate("vii / viii", ParseMode::Lenient, Remainders::Twelfths).unwrap();
+        assert_eq!(
+            answer.show(FormatOptions::new().lowercase(true), Remainders::Twelfths),
+            "about s···· (0 10/12)"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let classical = |input| calc(input, Remainders::Classical);
+        assert_eq!(
+            classical("V - X"),
+            "error: V - X: roman numerals can only represent positive numbers at column 3"
+        );
+        assert_eq!(
+            classical("IIII + I"),
+            "error: \"IIII\": 'I' repeated more than three times at position 3 at column 1"
+        );
+        assert_eq!(classical("X +"), "error: expected a number at column 4");
+        assert_eq!(classical("(X + I"), "error: expected ')' at column 7");
+        assert_eq!(classical("X + I)"), "error: unmatched ')' at column 6");
+        assert_eq!(classical("X I"), "error: expected an operator at column 3");
+        assert_eq!(classical("X % I"), "error: unexpected '%' at column 3");
+        assert_eq!(
+            classical("* X"),
+            "error: expected a number before '*' at column 1"
+        );
+        assert_eq!(
+            classical("0 + I"),
+            "error: \"0\": roman numerals can only represent positive numbers at column 1"
+        );
+        assert_eq!(
+            classical("(X / III) * II"),
+            "error: X / III leaves a remainder, which only the last division can show at column 4"
+        );
+    }
+
+    #[test]
+    fn tells_expressions_apart() {
+        assert!(is_expression("X + I"));
+        assert!(is_expression("(X)"));
+        assert!(is_expression("MMXXIII-1984"));
+        assert!(!is_expression("MCMLXXXIV"));
+        assert!(!is_expression("-4"));
+        assert!(!is_expression("1984"));
+    }
+}
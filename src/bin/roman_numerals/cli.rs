@@ -0,0 +1,678 @@
+use std::{
+    io::{self, Read, Write},
+    process::ExitCode,
+    str::FromStr,
+};
+
+use roman_numerals::{
+    FormatOptions, NumeralStyle, ParseMode, RomanNumeral, TextDirection, TextOptions, VinculumStyle,
+};
+
+use crate::calc::Remainders;
+use output::{Direction, Format, Record};
+use quiz::{Difficulty, QuizSettings};
+
+pub(crate) mod output;
+mod quiz;
+
+pub(crate) const HELP: &str = "\
+Converts between integers and roman numerals.
+
+Usage:
+  roman_numerals [--format <FORMAT>]        a prompt converting whichever way the input
+                                            suggests, or one line at a time when stdin
+                                            isn't a terminal. The prompt also works out
+                                            sums like (C - IX) / III, see :help in it
+  roman_numerals --twelfths                 the prompt, showing what's left over from a
+                                            division in twelfths, like XXX····
+  roman_numerals to-roman [OPTIONS] <INTEGER>...
+  roman_numerals from-roman [--lenient | --vinculum] <NUMERAL>...
+  roman_numerals validate <NUMERAL>...      exits with 1 unless every numeral is canonical
+  roman_numerals explain <INPUT>...         shows each step of converting numerals or
+                                            integers, as text or json
+  roman_numerals range [OPTIONS] [--step <N>] <FROM> <TO>
+  roman_numerals convert-text [OPTIONS]     rewrites the numerals in stdin as integers
+  roman_numerals quiz [OPTIONS]             practice converting, with missed questions
+                                            asked again
+  roman_numerals help
+
+Options for writing numerals:
+  --lowercase             mcmlxxxiv rather than MCMLXXXIV
+  --additive              IIII and VIIII rather than IV and IX
+  --clock-face            IIII rather than IV, but IX as usual
+  --j-terminal            a final I written as J, like viij
+  --vinculum[=<STYLE>]    bars for thousands from 4000 on, STYLE is unicode (the
+                          default), underscore or parentheses
+
+Options for reading numerals:
+  --lenient               sum the symbols even if they aren't in canonical order
+  --vinculum              read bars for thousands, in any style
+
+Options for convert-text, which also takes the options for writing numerals:
+  --to-numerals           rewrite integers in square brackets, like [14], as numerals
+                          instead
+  --read-lowercase        also read numerals written in lowercase
+  --min-len <N>           skip numerals shorter than N letters, 2 by default
+  --context <WORD>        always read numerals right after WORD, on top of words like
+                          chapter, part and louis
+  --ignore <WORD>         skip WORD, on top of words like MIX and CD
+
+Options for quiz:
+  --difficulty <LEVEL>    easy (up to 39, the default), medium (399) or hard (3999)
+  --min <N>, --max <N>    ask about values from N or up to N instead
+  --to-roman              only ask for numerals
+  --from-roman            only ask for integers
+  --questions <N>         how many to ask, 10 by default
+  --seed <N>              replay a session, the seed is shown at the end of each one
+
+Options for every subcommand but convert-text and quiz:
+  --format <FORMAT>       one record per input as json (one object per line), csv or
+                          tsv, instead of text. Records have the input, the direction,
+                          the value, the canonical numeral, whether a numeral input was
+                          canonical and the error's kind, position and message";
+
+// what the arguments asked for
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command {
+    Prompt(Remainders),
+    Help,
+    ToRoman {
+        options: FormatOptions,
+        integers: Vec<String>,
+    },
+    FromRoman {
+        mode: ParseMode,
+        vinculum: bool,
+        numerals: Vec<String>,
+    },
+    Validate {
+        numerals: Vec<String>,
+    },
+    Range {
+        options: FormatOptions,
+        from: u32,
+        to: u32,
+        step: usize,
+    },
+    Explain {
+        inputs: Vec<String>,
+    },
+    Quiz(QuizSettings),
+    ConvertText {
+        direction: TextDirection,
+        options: FormatOptions,
+        lowercase: bool,
+        min_len: Option<usize>,
+        context: Vec<String>,
+        ignore: Vec<String>,
+    },
+}
+
+// the options that change how numerals are written, `None` if `arg` isn't one of them
+fn format_option(options: FormatOptions, arg: &str) -> Option<Result<FormatOptions, String>> {
+    let options = match arg {
+        "--lowercase" => options.lowercase(true),
+        "--additive" => options.style(NumeralStyle::Additive),
+        "--clock-face" => options.style(NumeralStyle::ClockFace),
+        "--j-terminal" => options.j_terminal(true),
+        "--vinculum" | "--vinculum=unicode" => options.vinculum(Some(VinculumStyle::Unicode)),
+        "--vinculum=underscore" => options.vinculum(Some(VinculumStyle::Underscore)),
+        "--vinculum=parentheses" => options.vinculum(Some(VinculumStyle::Parentheses)),
+        _ if arg.starts_with("--vinculum=") => {
+            return Some(Err(format!("unknown vinculum style in {arg:?}")));
+        }
+        _ => return None,
+    };
+    Some(Ok(options))
+}
+
+// anything starting with a dash that isn't a negative number
+fn is_option(arg: &str) -> bool {
+    arg.starts_with('-') && arg.parse::<i64>().is_err()
+}
+
+fn parse_bound(name: &str, arg: Option<String>) -> Result<u32, String> {
+    let arg = arg.ok_or_else(|| format!("range needs <{name}>"))?;
+    let bound = arg
+        .parse()
+        .map_err(|err| format!("invalid <{name}> {arg:?}: {err}"))?;
+    // 0 has no numeral, the same as for to-roman
+    match RomanNumeral::with_value(bound) {
+        Ok(_) => Ok(bound),
+        Err(err) => Err(format!("{arg:?}: {err}")),
+    }
+}
+
+// the value after an option like --min
+fn option_value<T: FromStr>(option: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{option} needs a value"))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value {value:?} for {option}"))
+}
+
+fn parse_format(arg: Option<&str>) -> Result<Format, String> {
+    arg.ok_or("--format needs a value")?.parse()
+}
+
+// the command and the format to write its records in, `None` for text
+pub(crate) fn parse_args(
+    args: impl IntoIterator<Item = String>,
+) -> Result<(Command, Option<Format>), String> {
+    let mut args = args.into_iter();
+    let mut format = None;
+    let mut remainders = Remainders::Classical;
+    // --format can come before the subcommand too, or on its own for the prompt
+    let subcommand = loop {
+        match args.next() {
+            None => return Ok((Command::Prompt(remainders), format)),
+            Some(arg) if arg == "--twelfths" => remainders = Remainders::Twelfths,
+            Some(arg) if arg == "--format" => format = Some(parse_format(args.next().as_deref())?),
+            Some(arg) => match arg.strip_prefix("--format=") {
+                Some(value) => format = Some(parse_format(Some(value))?),
+                None => break arg,
+            },
+        }
+    };
+    if remainders == Remainders::Twelfths {
+        return Err("--twelfths is only for the prompt".to_string());
+    }
+
+    let mut options = FormatOptions::new();
+    let mut mode = ParseMode::Strict;
+    let mut vinculum = false;
+    let mut step = None;
+    let mut direction = TextDirection::ToIntegers;
+    let mut lowercase = false;
+    let mut min_len = None;
+    let mut context = Vec::new();
+    let mut ignore = Vec::new();
+    let mut difficulty = Difficulty::Easy;
+    let mut quiz = QuizSettings {
+        min: 1,
+        max: 0,
+        direction: None,
+        questions: 10,
+        seed: None,
+    };
+    let mut max = None;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        if !is_option(&arg) {
+            positional.push(arg);
+            continue;
+        }
+        match (subcommand.as_str(), arg.as_str()) {
+            (_, "--help" | "-h") => return Ok((Command::Help, format)),
+            (_, "--format") => format = Some(parse_format(args.next().as_deref())?),
+            (_, arg) if arg.starts_with("--format=") => {
+                format = Some(parse_format(arg.strip_prefix("--format="))?);
+            }
+            ("convert-text", "--read-lowercase") => lowercase = true,
+            ("to-roman" | "range" | "convert-text", arg)
+                if format_option(options, arg).is_some() =>
+            {
+                options = format_option(options, arg).expect("checked to be some")?;
+            }
+            ("from-roman", "--lenient") => mode = ParseMode::Lenient,
+            ("from-roman", "--vinculum") => vinculum = true,
+            ("range", "--step") => {
+                let n = args.next().ok_or("--step needs a value")?;
+                step = match n.parse() {
+                    Ok(0) | Err(_) => return Err(format!("invalid step {n:?}")),
+                    Ok(n) => Some(n),
+                };
+            }
+            ("convert-text", "--to-numerals") => direction = TextDirection::ToNumerals,
+            ("convert-text", "--min-len") => {
+                let n = args.next().ok_or("--min-len needs a value")?;
+                min_len = Some(n.parse().map_err(|_| format!("invalid length {n:?}"))?);
+            }
+            ("convert-text", "--context") => {
+                context.push(args.next().ok_or("--context needs a word")?);
+            }
+            ("convert-text", "--ignore") => {
+                ignore.push(args.next().ok_or("--ignore needs a word")?)
+            }
+            ("quiz", "--difficulty") => difficulty = option_value(&arg, args.next())?,
+            ("quiz", "--min") => quiz.min = option_value(&arg, args.next())?,
+            ("quiz", "--max") => max = Some(option_value(&arg, args.next())?),
+            ("quiz", "--to-roman") => quiz.direction = Some(Direction::ToRoman),
+            ("quiz", "--from-roman") => quiz.direction = Some(Direction::FromRoman),
+            ("quiz", "--questions") => quiz.questions = option_value(&arg, args.next())?,
+            ("quiz", "--seed") => quiz.seed = Some(option_value(&arg, args.next())?),
+            (_, "--") => positional.extend(args.by_ref()),
+            (subcommand, arg) => return Err(format!("unknown option {arg:?} for {subcommand}")),
+        }
+    }
+
+    let command = match subcommand.as_str() {
+        "help" | "--help" | "-h" => Command::Help,
+        "to-roman" => Command::ToRoman {
+            options,
+            integers: positional,
+        },
+        // the vinculum parser only reads canonical groups
+        "from-roman" if vinculum && mode == ParseMode::Lenient => {
+            return Err("--lenient and --vinculum can't be used together".to_string());
+        }
+        "from-roman" => Command::FromRoman {
+            mode,
+            vinculum,
+            numerals: positional,
+        },
+        "validate" => Command::Validate {
+            numerals: positional,
+        },
+        "quiz" => {
+            quiz.max = max.unwrap_or(difficulty.max());
+            if quiz.min == 0 || quiz.min > quiz.max {
+                return Err(format!("no numerals from {} to {}", quiz.min, quiz.max));
+            }
+            if quiz.questions == 0 {
+                return Err("a quiz needs at least one question".to_string());
+            }
+            if let Some(extra) = positional.first() {
+                return Err(format!("unexpected argument {extra:?}"));
+            }
+            if format.is_some() {
+                return Err("quiz only writes text".to_string());
+            }
+            Command::Quiz(quiz)
+        }
+        "explain" => {
+            if let Some(Format::Csv | Format::Tsv) = format {
+                return Err("explain only writes text or json".to_string());
+            }
+            Command::Explain { inputs: positional }
+        }
+        "range" => {
+            let mut bounds = positional.into_iter();
+            let from = parse_bound("FROM", bounds.next())?;
+            let to = parse_bound("TO", bounds.next())?;
+            if let Some(extra) = bounds.next() {
+                return Err(format!("unexpected argument {extra:?}"));
+            }
+            return Ok((
+                Command::Range {
+                    options,
+                    from,
+                    to,
+                    step: step.unwrap_or(1),
+                },
+                format,
+            ));
+        }
+        "convert-text" => {
+            if let Some(extra) = positional.first() {
+                return Err(format!(
+                    "unexpected argument {extra:?}, the text is read from stdin"
+                ));
+            }
+            if format.is_some() {
+                return Err("convert-text only writes text".to_string());
+            }
+            Command::ConvertText {
+                direction,
+                options,
+                lowercase,
+                min_len,
+                context,
+                ignore,
+            }
+        }
+        other => return Err(format!("unknown subcommand {other:?}")),
+    };
+    match &command {
+        Command::ToRoman { integers: args, .. }
+        | Command::FromRoman { numerals: args, .. }
+        | Command::Validate { numerals: args }
+        | Command::Explain { inputs: args }
+            if args.is_empty() =>
+        {
+            Err(format!("{subcommand} needs at least one argument"))
+        }
+        _ => Ok((command, format)),
+    }
+}
+
+// a record for each argument, or each numeral in the range
+fn records(command: &Command) -> Box<dyn Iterator<Item = Record> + '_> {
+    match command {
+        Command::Prompt(_) | Command::Help | Command::Quiz(_) | Command::ConvertText { .. } => {
+            unreachable!("only subcommands converting arguments have records")
+        }
+        Command::Explain { inputs } => Box::new(inputs.iter().map(|arg| Record::guess(arg))),
+        Command::ToRoman { integers, .. } => {
+            Box::new(integers.iter().map(|arg| Record::to_roman(arg)))
+        }
+        &Command::FromRoman {
+            mode,
+            vinculum,
+            ref numerals,
+        } => Box::new(
+            numerals
+                .iter()
+                .map(move |arg| Record::from_roman(arg, mode, vinculum)),
+        ),
+        Command::Validate { numerals } => Box::new(
+            numerals
+                .iter()
+                .map(|arg| Record::from_roman(arg, ParseMode::Strict, false)),
+        ),
+        &Command::Range { from, to, step, .. } => {
+            let numerals = RomanNumeral::range(from.min(to)..=from.max(to));
+            let numerals: Box<dyn Iterator<Item = RomanNumeral>> = if from <= to {
+                Box::new(numerals.step_by(step))
+            } else {
+                Box::new(numerals.rev().step_by(step))
+            };
+            Box::new(numerals.map(|r| Record {
+                input: r.to_int().to_string(),
+                direction: Direction::ToRoman,
+                result: Ok(r),
+                canonical: None,
+            }))
+        }
+    }
+}
+
+// the text line for a record that converted
+fn text(command: &Command, input: &str, r: RomanNumeral) -> String {
+    match *command {
+        Command::Prompt(_)
+        | Command::Help
+        | Command::Quiz(_)
+        | Command::ConvertText { .. }
+        | Command::Explain { .. } => {
+            unreachable!("only subcommands converting arguments have records")
+        }
+        Command::ToRoman { options, .. } => r.to_roman_numeral_string_with(options),
+        Command::FromRoman { .. } => r.to_int().to_string(),
+        Command::Validate { .. } => format!("{input}: ok, {}", r.to_int()),
+        Command::Range {
+            options, from, to, ..
+        } => {
+            let width = from.max(to).to_string().len();
+            format!("{:>width$}  {}", r.to_int(), r.display_with(options))
+        }
+    }
+}
+
+// rewrites the numerals in stdin, the default context and ignore words are kept and added to
+fn convert_stdin(command: &Command) -> ExitCode {
+    let Command::ConvertText {
+        direction,
+        options,
+        lowercase,
+        min_len,
+        context,
+        ignore,
+    } = command
+    else {
+        unreachable!("only called for convert-text")
+    };
+    let mut text = String::new();
+    if let Err(err) = io::stdin().read_to_string(&mut text) {
+        eprintln!("error: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    let context: Vec<&str> = TextOptions::DEFAULT_CONTEXT
+        .iter()
+        .copied()
+        .chain(context.iter().map(String::as_str))
+        .collect();
+    let ignore: Vec<&str> = TextOptions::DEFAULT_IGNORE
+        .iter()
+        .copied()
+        .chain(ignore.iter().map(String::as_str))
+        .collect();
+    let mut text_options = TextOptions::new()
+        .direction(*direction)
+        .lowercase(*lowercase)
+        .context(&context)
+        .ignore(&ignore)
+        .format(*options);
+    if let Some(min_len) = *min_len {
+        text_options = text_options.min_len(min_len);
+    }
+    let converted = roman_numerals::convert_text(&text, text_options);
+    match io::stdout().write_all(converted.as_bytes()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::FAILURE,
+    }
+}
+
+// runs every subcommand but the prompt. In text, each argument that fails is reported on
+// stderr and the rest carry on, other formats write a record for failures too
+pub(crate) fn run(command: Command, format: Option<Format>) -> ExitCode {
+    let mut out = io::stdout().lock();
+    if command == Command::Help {
+        let _ = writeln!(out, "{HELP}");
+        return ExitCode::SUCCESS;
+    }
+    match command {
+        Command::ConvertText { .. } => return convert_stdin(&command),
+        Command::Quiz(settings) => {
+            return match quiz::run(settings, io::stdin().lock(), out) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(_) => ExitCode::FAILURE,
+            };
+        }
+        _ => {}
+    }
+
+    if let Some(format) = format {
+        if output::write_header(&mut out, format).is_err() {
+            return ExitCode::FAILURE;
+        }
+    }
+    let mut failed = false;
+    for record in records(&command) {
+        let written = match (&command, format, &record.result) {
+            (Command::Explain { .. }, format, _) => {
+                output::write_explanation(&mut out, format, &record)
+            }
+            (_, Some(format), _) => output::write_record(&mut out, format, &record),
+            (_, None, &Ok(r)) => writeln!(out, "{}", text(&command, &record.input, r)),
+            (_, None, Err(_)) => Ok(()),
+        };
+        if written.is_err() {
+            // most likely a closed pipe, nobody is reading any more
+            return ExitCode::FAILURE;
+        }
+        if let Err(err) = &record.result {
+            if format.is_none() {
+                eprintln!("{:?}: {err}", record.input);
+            }
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roman_numerals::{FormatOptions, NumeralStyle, ParseMode, TextDirection, VinculumStyle};
+
+    use super::{
+        output::{Direction, Format},
+        parse_args,
+        quiz::QuizSettings,
+        Command, Remainders,
+    };
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|arg| arg.to_string())).map(|(command, _)| command)
+    }
+
+    fn format(args: &[&str]) -> Result<Option<Format>, String> {
+        parse_args(args.iter().map(|arg| arg.to_string())).map(|(_, format)| format)
+    }
+
+    #[test]
+    fn subcommands() {
+        assert_eq!(parse(&[]), Ok(Command::Prompt(Remainders::Classical)));
+        assert_eq!(
+            parse(&["--twelfths"]),
+            Ok(Command::Prompt(Remainders::Twelfths))
+        );
+        assert_eq!(parse(&["--help"]), Ok(Command::Help));
+        assert_eq!(parse(&["range", "-h"]), Ok(Command::Help));
+        assert_eq!(
+            parse(&[
+                "to-roman",
+                "--lowercase",
+                "1984",
+                "-4",
+                "--vinculum=underscore"
+            ]),
+            Ok(Command::ToRoman {
+                options: FormatOptions::new()
+                    .lowercase(true)
+                    .vinculum(Some(VinculumStyle::Underscore)),
+                integers: vec!["1984".to_string(), "-4".to_string()],
+            })
+        );
+        assert_eq!(
+            parse(&["from-roman", "--lenient", "IIII", "--", "--lenient"]),
+            Ok(Command::FromRoman {
+                mode: ParseMode::Lenient,
+                vinculum: false,
+                numerals: vec!["IIII".to_string(), "--lenient".to_string()],
+            })
+        );
+        assert_eq!(
+            parse(&["range", "--additive", "--step", "5", "1", "50"]),
+            Ok(Command::Range {
+                options: FormatOptions::new().style(NumeralStyle::Additive),
+                from: 1,
+                to: 50,
+                step: 5,
+            })
+        );
+        assert_eq!(
+            parse(&[
+                "convert-text",
+                "--to-numerals",
+                "--lowercase",
+                "--read-lowercase",
+                "--min-len",
+                "3",
+                "--context",
+                "page",
+                "--ignore",
+                "DIV"
+            ]),
+            Ok(Command::ConvertText {
+                direction: TextDirection::ToNumerals,
+                options: FormatOptions::new().lowercase(true),
+                lowercase: true,
+                min_len: Some(3),
+                context: vec!["page".to_string()],
+                ignore: vec!["DIV".to_string()],
+            })
+        );
+        assert_eq!(
+            parse(&["convert-text", "--read-lowercase"]),
+            Ok(Command::ConvertText {
+                direction: TextDirection::ToIntegers,
+                options: FormatOptions::new(),
+                lowercase: true,
+                min_len: None,
+                context: Vec::new(),
+                ignore: Vec::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn quiz_settings() {
+        assert_eq!(
+            parse(&[
+                "quiz",
+                "--difficulty",
+                "medium",
+                "--from-roman",
+                "--seed",
+                "7"
+            ]),
+            Ok(Command::Quiz(QuizSettings {
+                min: 1,
+                max: 399,
+                direction: Some(Direction::FromRoman),
+                questions: 10,
+                seed: Some(7),
+            }))
+        );
+        assert_eq!(
+            parse(&["quiz", "--max", "20", "--min", "10", "--questions", "3"]),
+            Ok(Command::Quiz(QuizSettings {
+                min: 10,
+                max: 20,
+                direction: None,
+                questions: 3,
+                seed: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(format(&[]), Ok(None));
+        assert_eq!(format(&["--format", "json"]), Ok(Some(Format::Json)));
+        assert_eq!(
+            parse(&["--format", "json"]),
+            Ok(Command::Prompt(Remainders::Classical))
+        );
+        assert_eq!(
+            format(&["--format=csv", "validate", "IV"]),
+            Ok(Some(Format::Csv))
+        );
+        assert_eq!(
+            format(&["to-roman", "4", "--format", "tsv"]),
+            Ok(Some(Format::Tsv))
+        );
+    }
+
+    #[test]
+    fn mistakes() {
+        for args in [
+            &["to-roman"][..],
+            &["to-roman", "--lenient", "4"],
+            &["from-roman", "--lowercase", "IV"],
+            &["to-roman", "--vinculum=bars", "4"],
+            &["range", "1"],
+            &["range", "1", "x"],
+            &["range", "1", "2", "3"],
+            &["range", "0", "5"],
+            &["range", "1", "0"],
+            &["from-roman", "--lenient", "--vinculum", "IIII"],
+            &["range", "--step", "0", "1", "2"],
+            &["roman"],
+            &["--format"],
+            &["--format", "xml"],
+            &["validate", "--format=yaml", "IV"],
+            &["convert-text", "chapter.txt"],
+            &["explain"],
+            &["quiz", "--min", "0"],
+            &["quiz", "--min", "50", "--max", "10"],
+            &["quiz", "--difficulty", "impossible"],
+            &["quiz", "--questions", "0"],
+            &["quiz", "--seed"],
+            &["--twelfths", "to-roman", "4"],
+            &["explain", "--format", "csv", "IV"],
+            &["convert-text", "--format", "json"],
+            &["convert-text", "--min-len", "two"],
+            &["to-roman", "--to-numerals", "4"],
+        ] {
+            assert!(parse(args).is_err(), "expected {args:?} to be rejected");
+        }
+    }
+}
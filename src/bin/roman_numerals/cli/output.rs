@@ -0,0 +1,478 @@
+use std::{
+    fmt,
+    io::{self, Write},
+    num::{IntErrorKind, ParseIntError},
+    str::FromStr,
+};
+
+use roman_numerals::{Operation, ParseMode, Position, RomanNumeral, RomanNumeralError};
+
+// machine readable ways of writing records, the text output is specific to each subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    // one object per line
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!("unknown format {s:?}, expected json, csv or tsv")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    ToRoman,
+    FromRoman,
+}
+
+impl Direction {
+    fn name(self) -> &'static str {
+        match self {
+            Direction::ToRoman => "to-roman",
+            Direction::FromRoman => "from-roman",
+        }
+    }
+}
+
+// why an input couldn't be converted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Failure {
+    Numeral(RomanNumeralError),
+    Integer(ParseIntError),
+}
+
+impl Failure {
+    fn kind(&self) -> &'static str {
+        match self {
+            Failure::Numeral(err) => match err {
+                RomanNumeralError::NonPositiveNumeral => "non-positive",
+                RomanNumeralError::EmptyInput => "empty-input",
+                RomanNumeralError::InvalidChar(..) => "invalid-char",
+                RomanNumeralError::IllegalSubtractivePair(..) => "illegal-subtractive-pair",
+                RomanNumeralError::RepeatedSymbol(..) => "repeated-symbol",
+                RomanNumeralError::TooManyRepeats(..) => "too-many-repeats",
+                RomanNumeralError::OutOfOrder(..) => "out-of-order",
+                RomanNumeralError::TooManyUnciae(..) => "too-many-unciae",
+                RomanNumeralError::BufferTooSmall => "buffer-too-small",
+                RomanNumeralError::Overflow => "overflow",
+            },
+            Failure::Integer(_) => "invalid-integer",
+        }
+    }
+
+    fn position(&self) -> Option<Position> {
+        match self {
+            Failure::Numeral(err) => err.position(),
+            Failure::Integer(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Numeral(err) => err.fmt(f),
+            Failure::Integer(err) => err.fmt(f),
+        }
+    }
+}
+
+// everything known about converting one input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Record {
+    pub(crate) input: String,
+    pub(crate) direction: Direction,
+    pub(crate) result: Result<RomanNumeral, Failure>,
+    // whether a numeral input follows the strict rules, `None` for integers
+    pub(crate) canonical: Option<bool>,
+}
+
+impl Record {
+    pub(crate) fn to_roman(input: &str) -> Record {
+        let result = match input.parse::<i64>() {
+            Ok(int_val) => RomanNumeral::try_from(int_val).map_err(Failure::Numeral),
+            // integers too large for an i64 are still integers
+            Err(err) => Err(match err.kind() {
+                IntErrorKind::PosOverflow => Failure::Numeral(RomanNumeralError::Overflow),
+                IntErrorKind::NegOverflow => {
+                    Failure::Numeral(RomanNumeralError::NonPositiveNumeral)
+                }
+                _ => Failure::Integer(err),
+            }),
+        };
+        Record {
+            input: input.to_string(),
+            direction: Direction::ToRoman,
+            result,
+            canonical: None,
+        }
+    }
+
+    pub(crate) fn from_roman(input: &str, mode: ParseMode, vinculum: bool) -> Record {
+        let result = if vinculum {
+            RomanNumeral::from_vinculum_str(input)
+        } else {
+            RomanNumeral::parse(input, mode)
+        };
+        let canonical = match mode {
+            ParseMode::Lenient if !vinculum => RomanNumeral::from_str(input).is_ok(),
+            _ => result.is_ok(),
+        };
+        Record {
+            input: input.to_string(),
+            direction: Direction::FromRoman,
+            result: result.map_err(Failure::Numeral),
+            canonical: Some(canonical),
+        }
+    }
+
+    // reads `input` as a numeral, or failing that as an integer to write as one
+    pub(crate) fn guess(input: &str) -> Record {
+        Record::guess_with(input, ParseMode::Strict, false)
+    }
+
+    // like `guess`, reading numerals like `from_roman` does
+    pub(crate) fn guess_with(input: &str, mode: ParseMode, vinculum: bool) -> Record {
+        let record = Record::from_roman(input, mode, vinculum);
+        if record.result.is_err() && input.parse::<i64>().is_ok() {
+            return Record::to_roman(input);
+        }
+        record
+    }
+
+    fn fields(&self) -> [Option<String>; FIELDS.len()] {
+        let (value, numeral) = match &self.result {
+            Ok(r) => (Some(r.to_int().to_string()), Some(r.to_string())),
+            Err(_) => (None, None),
+        };
+        let err = self.result.as_ref().err();
+        let position = err.and_then(Failure::position);
+        [
+            Some(self.input.clone()),
+            Some(self.direction.name().to_string()),
+            value,
+            numeral,
+            self.canonical.map(|c| c.to_string()),
+            err.map(|err| err.kind().to_string()),
+            position.map(|p| p.byte.to_string()),
+            position.map(|p| p.char.to_string()),
+            err.map(|err| err.to_string()),
+        ]
+    }
+}
+
+const FIELDS: [&str; 9] = [
+    "input",
+    "direction",
+    "value",
+    "numeral",
+    "canonical",
+    "error_kind",
+    "error_byte",
+    "error_char",
+    "error_message",
+];
+
+fn write_json_str(out: &mut impl Write, s: &str) -> io::Result<()> {
+    write!(out, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(out, "\\\"")?,
+            '\\' => write!(out, "\\\\")?,
+            '\n' => write!(out, "\\n")?,
+            '\r' => write!(out, "\\r")?,
+            '\t' => write!(out, "\\t")?,
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{c}")?,
+        }
+    }
+    write!(out, "\"")
+}
+
+// the steps of a conversion that worked, see `write_explanation`
+fn write_json_steps(out: &mut impl Write, record: &Record) -> io::Result<()> {
+    write!(out, ",\"steps\":[")?;
+    match (record.direction, &record.result) {
+        (Direction::FromRoman, Ok(_)) => {
+            let steps = RomanNumeral::parse_steps(&record.input)
+                .into_iter()
+                .flatten();
+            for (i, step) in steps.enumerate() {
+                let separator = if i > 0 { "," } else { "" };
+                let operation = match step.operation {
+                    Operation::Add => "add",
+                    Operation::Subtract => "subtract",
+                };
+                write!(out, "{separator}{{\"symbol\":")?;
+                write_json_str(out, step.symbol.encode_utf8(&mut [0; 4]))?;
+                write!(
+                    out,
+                    ",\"position\":{{\"byte\":{},\"char\":{}}},\"value\":{},\"operation\":\"{operation}\",\"total\":{}}}",
+                    step.position.byte, step.position.char, step.value, step.total
+                )?;
+            }
+        }
+        (Direction::ToRoman, Ok(r)) => {
+            for (i, step) in r.format_steps().enumerate() {
+                let separator = if i > 0 { "," } else { "" };
+                write!(
+                    out,
+                    "{separator}{{\"symbols\":\"{}\",\"value\":{},\"remaining\":{}}}",
+                    step.symbols, step.value, step.remaining
+                )?;
+            }
+        }
+        (_, Err(_)) => {}
+    }
+    write!(out, "]")
+}
+
+fn write_json(out: &mut impl Write, record: &Record, steps: bool) -> io::Result<()> {
+    write!(out, "{{\"input\":")?;
+    write_json_str(out, &record.input)?;
+    write!(out, ",\"direction\":\"{}\"", record.direction.name())?;
+    match &record.result {
+        Ok(r) => write!(out, ",\"value\":{},\"numeral\":\"{r}\"", r.to_int())?,
+        Err(_) => write!(out, ",\"value\":null,\"numeral\":null")?,
+    }
+    match record.canonical {
+        Some(canonical) => write!(out, ",\"canonical\":{canonical}")?,
+        None => write!(out, ",\"canonical\":null")?,
+    }
+    match &record.result {
+        Ok(_) => write!(out, ",\"error\":null")?,
+        Err(err) => {
+            write!(
+                out,
+                ",\"error\":{{\"kind\":\"{}\",\"position\":",
+                err.kind()
+            )?;
+            match err.position() {
+                Some(p) => write!(out, "{{\"byte\":{},\"char\":{}}}", p.byte, p.char)?,
+                None => write!(out, "null")?,
+            }
+            write!(out, ",\"message\":")?;
+            write_json_str(out, &err.to_string())?;
+            write!(out, "}}")?;
+        }
+    }
+    if steps {
+        write_json_steps(out, record)?;
+    }
+    writeln!(out, "}}")
+}
+
+// quoted only when it has to be, as in RFC 4180
+fn write_csv_field(out: &mut impl Write, field: &str) -> io::Result<()> {
+    if field.contains([',', '"', '\n', '\r']) {
+        write!(out, "\"{}\"", field.replace('"', "\"\""))
+    } else {
+        write!(out, "{field}")
+    }
+}
+
+// tabs and line breaks can't be quoted in TSV, so they're escaped
+fn write_tsv_field(out: &mut impl Write, field: &str) -> io::Result<()> {
+    for c in field.chars() {
+        match c {
+            '\\' => write!(out, "\\\\")?,
+            '\t' => write!(out, "\\t")?,
+            '\n' => write!(out, "\\n")?,
+            '\r' => write!(out, "\\r")?,
+            c => write!(out, "{c}")?,
+        }
+    }
+    Ok(())
+}
+
+fn write_row<'a>(
+    out: &mut impl Write,
+    format: Format,
+    fields: impl IntoIterator<Item = &'a str>,
+) -> io::Result<()> {
+    let (separator, write_field): (_, fn(&mut _, &str) -> _) = match format {
+        Format::Json => unreachable!("JSON has no rows"),
+        Format::Csv => (",", write_csv_field),
+        Format::Tsv => ("\t", write_tsv_field),
+    };
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            write!(out, "{separator}")?;
+        }
+        write_field(out, field)?;
+    }
+    writeln!(out)
+}
+
+// the line naming the fields, if the format has one
+pub(crate) fn write_header(out: &mut impl Write, format: Format) -> io::Result<()> {
+    match format {
+        Format::Json => Ok(()),
+        Format::Csv | Format::Tsv => write_row(out, format, FIELDS),
+    }
+}
+
+// one line per record in every format, missing fields are null in JSON and empty otherwise
+pub(crate) fn write_record(
+    out: &mut impl Write,
+    format: Format,
+    record: &Record,
+) -> io::Result<()> {
+    match format {
+        Format::Json => write_json(out, record, false),
+        Format::Csv | Format::Tsv => {
+            let fields = record.fields();
+            write_row(
+                out,
+                format,
+                fields.iter().map(|f| f.as_deref().unwrap_or("")),
+            )
+        }
+    }
+}
+
+// how a conversion that worked was done, one step per line after the result in text, or the
+// record with its steps in JSON
+pub(crate) fn write_explanation(
+    out: &mut impl Write,
+    format: Option<Format>,
+    record: &Record,
+) -> io::Result<()> {
+    match (format, &record.result) {
+        (Some(Format::Json), _) => write_json(out, record, true),
+        (Some(Format::Csv | Format::Tsv), _) => unreachable!("explanations only come as json"),
+        (None, Err(_)) => Ok(()),
+        (None, Ok(r)) if record.direction == Direction::FromRoman => {
+            writeln!(out, "{} = {}", record.input, r.to_int())?;
+            let mut steps = RomanNumeral::parse_steps(&record.input)
+                .into_iter()
+                .flatten();
+            steps.try_for_each(|step| writeln!(out, "  {step}"))
+        }
+        (None, Ok(r)) => {
+            writeln!(out, "{} = {r}", record.input)?;
+            r.format_steps()
+                .try_for_each(|step| writeln!(out, "  {step}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roman_numerals::ParseMode;
+
+    use super::{write_explanation, write_header, write_record, Format, Record};
+
+    fn written(format: Format, records: &[Record]) -> String {
+        let mut out = Vec::new();
+        write_header(&mut out, format).unwrap();
+        for record in records {
+            write_record(&mut out, format, record).unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn records() {
+        let records = [
+            Record::to_roman("1984"),
+            Record::from_roman("IIII", ParseMode::Lenient, false),
+            Record::guess("MCMC"),
+            Record::to_roman("99999999999999999999"),
+        ];
+        assert_eq!(
+            written(Format::Json, &records),
+            concat!(
+                r#"{"input":"1984","direction":"to-roman","value":1984,"numeral":"MCMLXXXIV","canonical":null,"error":null}"#,
+                "\n",
+                r#"{"input":"IIII","direction":"from-roman","value":4,"numeral":"IV","canonical":false,"error":null}"#,
+                "\n",
+                r#"{"input":"MCMC","direction":"from-roman","value":null,"numeral":null,"canonical":false,"error":{"kind":"out-of-order","position":{"byte":3,"char":3},"message":"'C' is out of order at position 3"}}"#,
+                "\n",
+                r#"{"input":"99999999999999999999","direction":"to-roman","value":null,"numeral":null,"canonical":null,"error":{"kind":"overflow","position":null,"message":"the number is too large to be represented"}}"#,
+                "\n",
+            )
+        );
+        assert_eq!(
+            written(Format::Csv, &records[1..3]),
+            "input,direction,value,numeral,canonical,error_kind,error_byte,error_char,error_message\n\
+             IIII,from-roman,4,IV,false,,,,\n\
+             MCMC,from-roman,,,false,out-of-order,3,3,'C' is out of order at position 3\n"
+        );
+        assert_eq!(
+            written(Format::Tsv, &records[..1]),
+            "input\tdirection\tvalue\tnumeral\tcanonical\terror_kind\terror_byte\terror_char\terror_message\n\
+             1984\tto-roman\t1984\tMCMLXXXIV\t\t\t\t\t\n"
+        );
+        for input in ["ⅫI", "Mↂ"] {
+            let record = Record::from_roman(input, ParseMode::Lenient, false);
+            assert_eq!(record.canonical, Some(false), "{input}");
+        }
+    }
+
+    #[test]
+    fn explanations() {
+        let explained = |format, input| {
+            let mut out = Vec::new();
+            write_explanation(&mut out, format, &Record::guess(input)).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            explained(None, "XIV"),
+            "XIV = 14\n  add X (10), total 10\n  subtract I (1), total 9\n  add V (5), total 14\n"
+        );
+        assert_eq!(
+            explained(None, "14"),
+            "14 = XIV\n  X = 10, leaving 4\n  IV = 4, leaving 0\n"
+        );
+        assert_eq!(explained(None, "MCMC"), "");
+        assert_eq!(
+            explained(Some(Format::Json), "IV"),
+            concat!(
+                r#"{"input":"IV","direction":"from-roman","value":4,"numeral":"IV","canonical":true,"error":null,"steps":["#,
+                r#"{"symbol":"I","position":{"byte":0,"char":0},"value":1,"operation":"subtract","total":-1},"#,
+                r#"{"symbol":"V","position":{"byte":1,"char":1},"value":5,"operation":"add","total":4}]}"#,
+                "\n"
+            )
+        );
+        assert_eq!(
+            explained(Some(Format::Json), "6"),
+            concat!(
+                r#"{"input":"6","direction":"to-roman","value":6,"numeral":"VI","canonical":null,"error":null,"steps":["#,
+                r#"{"symbols":"V","value":5,"remaining":1},{"symbols":"I","value":1,"remaining":0}]}"#,
+                "\n"
+            )
+        );
+        assert!(explained(Some(Format::Json), "0").ends_with("\"steps\":[]}\n"));
+    }
+
+    #[test]
+    fn escaping() {
+        let records = [Record::guess("a,\"b\"\tc\\")];
+        let json = written(Format::Json, &records);
+        assert!(json.starts_with(r#"{"input":"a,\"b\"\tc\\","#), "{json}");
+        let csv = written(Format::Csv, &records);
+        assert!(
+            csv.lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("\"a,\"\"b\"\"\tc\\\","),
+            "{csv}"
+        );
+        let tsv = written(Format::Tsv, &records);
+        assert!(
+            tsv.lines().nth(1).unwrap().starts_with("a,\"b\"\\tc\\\\\t"),
+            "{tsv}"
+        );
+    }
+}
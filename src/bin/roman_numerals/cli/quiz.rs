@@ -0,0 +1,385 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    io::{self, BufRead, Write},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use roman_numerals::RomanNumeral;
+
+use super::output::Direction;
+
+// how many other questions come between missing one and being asked it again
+const REASK_AFTER: usize = 2;
+
+// presets for the range of values asked about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub(crate) fn max(self) -> u32 {
+        match self {
+            Difficulty::Easy => 39,
+            Difficulty::Medium => 399,
+            Difficulty::Hard => RomanNumeral::MAX_STANDARD.to_int(),
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!(
+                "unknown difficulty {s:?}, expected easy, medium or hard"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QuizSettings {
+    pub(crate) min: u32,
+    pub(crate) max: u32,
+    // both directions, picked at random, if `None`
+    pub(crate) direction: Option<Direction>,
+    pub(crate) questions: usize,
+    // taken from the clock if `None`
+    pub(crate) seed: Option<u64>,
+}
+
+// SplitMix64, small and good enough for picking questions, and the same everywhere so a
+// seed always replays the same session
+#[derive(Debug, Clone)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // a number in 0..n, close enough to uniform for anything n could be here
+    pub(crate) fn below(&mut self, n: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Question {
+    pub(crate) numeral: RomanNumeral,
+    // the direction the answer has to convert in
+    pub(crate) direction: Direction,
+}
+
+impl Question {
+    fn answer(&self) -> String {
+        match self.direction {
+            Direction::ToRoman => self.numeral.to_string(),
+            Direction::FromRoman => self.numeral.to_int().to_string(),
+        }
+    }
+
+    // why `answer` is wrong, read with the library's parser, `None` if it's right
+    fn mistake(&self, answer: &str) -> Option<String> {
+        if answer.is_empty() {
+            return Some("that's blank".to_string());
+        }
+        match self.direction {
+            Direction::ToRoman => match RomanNumeral::from_str(answer) {
+                Ok(n) if n == self.numeral => None,
+                Ok(n) => Some(format!("{answer} is {}", n.to_int())),
+                Err(err) => Some(err.to_string()),
+            },
+            Direction::FromRoman => match answer.parse::<u32>() {
+                Ok(v) if v == self.numeral.to_int() => None,
+                Ok(v) => match RomanNumeral::with_value(v) {
+                    Ok(n) => Some(format!("{v} is {n}")),
+                    Err(err) => Some(err.to_string()),
+                },
+                Err(err) => Some(err.to_string()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Question {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.direction {
+            Direction::ToRoman => write!(f, "what is {} as a numeral?", self.numeral.to_int()),
+            Direction::FromRoman => write!(f, "what is {} as an integer?", self.numeral),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Right { streak: u32 },
+    Wrong { mistake: String, answer: String },
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Right { streak } => write!(f, "right! streak {streak}"),
+            Outcome::Wrong { mistake, answer } => {
+                write!(f, "no, {mistake}. The answer is {answer}")
+            }
+        }
+    }
+}
+
+// a session of questions, with the ones missed asked again a little later
+#[derive(Debug, Clone)]
+pub(crate) struct Quiz {
+    settings: QuizSettings,
+    seed: u64,
+    rng: Rng,
+    // missed questions and the number of questions asked by the time they're due again
+    missed: VecDeque<(Question, usize)>,
+    current: Option<Question>,
+    asked: usize,
+    right: usize,
+    streak: u32,
+    best_streak: u32,
+}
+
+impl Quiz {
+    pub(crate) fn new(settings: QuizSettings) -> Quiz {
+        let seed = settings.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_nanos() as u64)
+        });
+        Quiz {
+            settings,
+            seed,
+            rng: Rng::new(seed),
+            missed: VecDeque::new(),
+            current: None,
+            asked: 0,
+            right: 0,
+            streak: 0,
+            best_streak: 0,
+        }
+    }
+
+    fn random_question(&mut self) -> Question {
+        let QuizSettings { min, max, .. } = self.settings;
+        let v = u64::from(min) + self.rng.below(u64::from(max - min) + 1);
+        let direction = self.settings.direction.unwrap_or_else(|| {
+            if self.rng.below(2) == 0 {
+                Direction::ToRoman
+            } else {
+                Direction::FromRoman
+            }
+        });
+        Question {
+            numeral: RomanNumeral::try_from(v).expect("the range is checked to start at 1"),
+            direction,
+        }
+    }
+
+    // `None` once every question has been asked
+    pub(crate) fn next_question(&mut self) -> Option<Question> {
+        let left = self.settings.questions - self.asked;
+        if left == 0 {
+            return None;
+        }
+        // near the end the missed questions are asked whether they're due or not
+        let due = self
+            .missed
+            .front()
+            .is_some_and(|&(_, due)| due <= self.asked || left <= self.missed.len());
+        let question = if due {
+            self.missed.pop_front().expect("checked to be there").0
+        } else {
+            self.random_question()
+        };
+        self.asked += 1;
+        self.current = Some(question);
+        Some(question)
+    }
+
+    pub(crate) fn answer(&mut self, answer: &str) -> Outcome {
+        let question = self.current.take().expect("a question has been asked");
+        match question.mistake(answer) {
+            None => {
+                self.right += 1;
+                self.streak += 1;
+                self.best_streak = self.best_streak.max(self.streak);
+                Outcome::Right {
+                    streak: self.streak,
+                }
+            }
+            Some(mistake) => {
+                self.streak = 0;
+                self.missed.push_back((question, self.asked + REASK_AFTER));
+                Outcome::Wrong {
+                    mistake,
+                    answer: question.answer(),
+                }
+            }
+        }
+    }
+
+    // the number of the question just asked
+    pub(crate) fn asked(&self) -> usize {
+        self.asked
+    }
+}
+
+impl fmt::Display for Quiz {
+    // the score so far, with the seed to replay the session with
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} right out of {}, best streak {} (--seed {})",
+            self.right, self.asked, self.best_streak, self.seed
+        )
+    }
+}
+
+// asks questions on `out` and reads the answers from `input` until the session is over or
+// the input runs out
+pub(crate) fn run(
+    settings: QuizSettings,
+    input: impl BufRead,
+    mut out: impl Write,
+) -> io::Result<()> {
+    let mut quiz = Quiz::new(settings);
+    let mut answers = input.lines();
+    while let Some(question) = quiz.next_question() {
+        write!(out, "{}. {question} ", quiz.asked())?;
+        out.flush()?;
+        let Some(answer) = answers.next().transpose()? else {
+            writeln!(out)?;
+            break;
+        };
+        writeln!(out, "{}", quiz.answer(answer.trim()))?;
+    }
+    writeln!(out, "{quiz}")
+}
+
+#[cfg(test)]
+mod tests {
+    use roman_numerals::RomanNumeral;
+
+    use super::{run, Direction, Outcome, Question, Quiz, QuizSettings, Rng};
+
+    fn settings(seed: u64) -> QuizSettings {
+        QuizSettings {
+            min: 1,
+            max: 39,
+            direction: None,
+            questions: 6,
+            seed: Some(seed),
+        }
+    }
+
+    #[test]
+    fn seeds_replay() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+            assert!(a.below(10) < 10);
+            b.below(10);
+        }
+
+        let session = |seed| {
+            let mut out = Vec::new();
+            run(settings(seed), "X\n4\n".as_bytes(), &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(session(42), session(42));
+        assert_ne!(session(42), session(43));
+    }
+
+    #[test]
+    fn checks_answers() {
+        let question = Question {
+            numeral: RomanNumeral::with_value(4).unwrap(),
+            direction: Direction::ToRoman,
+        };
+        assert_eq!(question.mistake("IV"), None);
+        assert_eq!(question.mistake("iv"), None);
+        assert_eq!(question.mistake("").unwrap(), "that's blank");
+        assert_eq!(question.mistake("VI").unwrap(), "VI is 6");
+        assert!(question.mistake("IIII").unwrap().contains("repeated"));
+        let question = Question {
+            direction: Direction::FromRoman,
+            ..question
+        };
+        assert_eq!(question.mistake("4"), None);
+        assert_eq!(question.mistake("6").unwrap(), "6 is VI");
+        assert!(question.mistake("IV").is_some());
+    }
+
+    #[test]
+    fn asks_missed_questions_again() {
+        let mut quiz = Quiz::new(settings(1));
+        let mut questions = Vec::new();
+        while let Some(question) = quiz.next_question() {
+            // miss the first question and get everything else right
+            let answer = if questions.is_empty() {
+                String::from("0")
+            } else {
+                question.answer()
+            };
+            if let Outcome::Right { streak } = quiz.answer(&answer) {
+                assert!(streak > 0);
+            }
+            questions.push(question);
+        }
+        assert_eq!(questions.len(), 6);
+        assert_eq!(questions[3], questions[0]);
+        assert_eq!(
+            quiz.to_string(),
+            "5 right out of 6, best streak 5 (--seed 1)"
+        );
+
+        // a question missed at the very end still comes back, in place of a new one
+        let mut quiz = Quiz::new(settings(1));
+        let mut questions = Vec::new();
+        while let Some(question) = quiz.next_question() {
+            let answer = if quiz.asked() == 5 {
+                String::from("0")
+            } else {
+                question.answer()
+            };
+            quiz.answer(&answer);
+            questions.push(question);
+        }
+        assert_eq!(questions[5], questions[4]);
+        assert!(quiz.to_string().starts_with("5 right out of 6"));
+    }
+
+    #[test]
+    fn ends_with_the_input() {
+        let mut out = Vec::new();
+        run(settings(3), "".as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("1. what is "), "{out}");
+        assert!(
+            out.ends_with("\n0 right out of 1, best streak 0 (--seed 3)\n"),
+            "{out}"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    process::ExitCode,
+};
+
+use cli::{
+    output::{self, Direction, Format, Record},
+    Command,
+};
+use repl::Modes;
+
+mod calc;
+mod cli;
+mod repl;
+
+// one conversion per line of input and nothing else. In text, failures go to stderr with a
+// "-" in their place on stdout so the two line up, other formats write a record for them,
+// either way they make the exit status non-zero once all the input has been read
+fn batch(format: Option<Format>) -> ExitCode {
+    let mut failed = false;
+    let mut out = io::stdout().lock();
+    if let Some(format) = format {
+        if output::write_header(&mut out, format).is_err() {
+            return ExitCode::FAILURE;
+        }
+    }
+    for (i, line) in io::stdin().lock().lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => {
+                eprintln!("error: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        let input = line.trim();
+        if input.is_empty() {
+            continue;
+        }
+        let record = Record::guess(input);
+        let written = match (format, record.direction, &record.result) {
+            (Some(format), ..) => output::write_record(&mut out, format, &record),
+            (None, Direction::FromRoman, Ok(r)) => writeln!(out, "{}", r.to_int()),
+            (None, Direction::ToRoman, Ok(r)) => writeln!(out, "{r}"),
+            (None, _, Err(err)) => {
+                eprintln!("line {}: {input:?}: {err}", i + 1);
+                writeln!(out, "-")
+            }
+        };
+        failed |= record.result.is_err();
+        if written.is_err() {
+            // most likely a closed pipe, nobody is reading any more
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn main() -> ExitCode {
+    match cli::parse_args(std::env::args().skip(1)) {
+        // records are meant for other programs, so they never come with a prompt
+        Ok((Command::Prompt(remainders), None)) if io::stdin().is_terminal() => {
+            repl::run(Modes::new(remainders))
+        }
+        Ok((Command::Prompt(_), format)) => batch(format),
+        Ok((command, format)) => cli::run(command, format),
+        Err(err) => {
+            eprintln!("error: {err}\n\nRun with --help for usage.");
+            ExitCode::from(2)
+        }
+    }
+}
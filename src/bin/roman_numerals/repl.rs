@@ -0,0 +1,393 @@
+use std::{env, fmt, fs, path::PathBuf, process::ExitCode};
+
+use roman_numerals::{FormatOptions, ParseMode, VinculumStyle};
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::DefaultHistory, validate::Validator, Context, Editor, Helper,
+};
+
+use crate::{
+    calc::{self, Remainders},
+    cli::output::{Direction, Failure, Record},
+};
+
+const HELP: &str = "\
+Type a numeral or an integer to convert it, or a sum like (C - IX) / III to work it out.
+The arrow keys edit the line and go through earlier ones, which are kept between sessions.
+
+Commands:
+  :help               this help
+  :mode               shows the modes in use
+  :mode <MODE>...     switches modes, separated by spaces or commas, any of
+                        uppercase, lowercase    how numerals are written
+                        strict, lenient         how numerals are read
+                        standard, vinculum      bars for thousands from 4000 on, strict only
+                        remainders, twelfths    how a division's remainder is shown
+  :quit               leaves, as do Ctrl-D and Ctrl-C";
+
+const COMMANDS: [&str; 3] = [":help", ":mode", ":quit"];
+
+// each mode along with the one it replaces
+const MODES: [[&str; 2]; 4] = [
+    ["uppercase", "lowercase"],
+    ["strict", "lenient"],
+    ["standard", "vinculum"],
+    ["remainders", "twelfths"],
+];
+
+// the styles the prompt reads and writes numerals in, switched with :mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Modes {
+    lowercase: bool,
+    mode: ParseMode,
+    vinculum: bool,
+    remainders: Remainders,
+}
+
+impl Modes {
+    pub(crate) fn new(remainders: Remainders) -> Modes {
+        Modes {
+            lowercase: false,
+            mode: ParseMode::Strict,
+            vinculum: false,
+            remainders,
+        }
+    }
+
+    // the modes after switching to each of `modes`, separated by spaces or commas, and none
+    // of them if any is unknown or they don't go together
+    fn with(mut self, modes: &str) -> Result<Modes, String> {
+        modes
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|mode| !mode.is_empty())
+            .try_for_each(|mode| self.set(mode))?;
+        // vinculum numerals are only read strictly, as with --vinculum
+        if self.vinculum && self.mode == ParseMode::Lenient {
+            return Err("lenient and vinculum can't be used together".to_string());
+        }
+        Ok(self)
+    }
+
+    fn set(&mut self, mode: &str) -> Result<(), String> {
+        match mode {
+            "uppercase" | "lowercase" => self.lowercase = mode == "lowercase",
+            "strict" => self.mode = ParseMode::Strict,
+            "lenient" => self.mode = ParseMode::Lenient,
+            "standard" | "vinculum" => self.vinculum = mode == "vinculum",
+            "remainders" => self.remainders = Remainders::Classical,
+            "twelfths" => self.remainders = Remainders::Twelfths,
+            _ => return Err(format!("unknown mode {mode:?}, see :help")),
+        }
+        Ok(())
+    }
+
+    fn format_options(&self) -> FormatOptions {
+        FormatOptions::new()
+            .lowercase(self.lowercase)
+            .vinculum(self.vinculum.then_some(VinculumStyle::Unicode))
+    }
+}
+
+impl fmt::Display for Modes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [written, read, vinculum, remainders] = MODES;
+        write!(
+            f,
+            "{}, {}, {}, {}",
+            written[usize::from(self.lowercase)],
+            read[usize::from(self.mode == ParseMode::Lenient)],
+            vinculum[usize::from(self.vinculum)],
+            remainders[usize::from(self.remainders == Remainders::Twelfths)]
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Reply {
+    Print(String),
+    Nothing,
+    Quit,
+}
+
+// what the prompt says to each line, apart from the terminal handling
+#[derive(Debug, Clone)]
+pub(crate) struct Session {
+    modes: Modes,
+}
+
+impl Session {
+    pub(crate) fn new(modes: Modes) -> Session {
+        Session { modes }
+    }
+
+    fn command(&mut self, command: &str) -> Reply {
+        let (name, rest) = command
+            .split_once(char::is_whitespace)
+            .unwrap_or((command, ""));
+        match name {
+            ":quit" | ":q" | ":exit" => Reply::Quit,
+            ":help" | ":h" => Reply::Print(HELP.to_string()),
+            ":mode" => match self.modes.with(rest) {
+                Ok(modes) => {
+                    self.modes = modes;
+                    Reply::Print(format!("modes: {}", self.modes))
+                }
+                Err(err) => Reply::Print(format!("error: {err}")),
+            },
+            _ => Reply::Print(format!("error: unknown command {command:?}, see :help")),
+        }
+    }
+
+    pub(crate) fn reply(&mut self, line: &str) -> Reply {
+        let input = line.trim();
+        let options = self.modes.format_options();
+        if input.is_empty() {
+            return Reply::Nothing;
+        }
+        if input.starts_with(':') {
+            return self.command(input);
+        }
+        if calc::is_expression(input) {
+            return Reply::Print(
+                match calc::evaluate(input, self.modes.mode, self.modes.remainders) {
+                    Ok(answer) => {
+                        format!("result: {}", answer.show(options, self.modes.remainders))
+                    }
+                    Err(err) => format!("error: {err}"),
+                },
+            );
+        }
+
+        let record = Record::guess_with(input, self.modes.mode, self.modes.vinculum);
+        Reply::Print(match (record.direction, record.result) {
+            (Direction::FromRoman, Ok(r)) => format!(
+                "value: {:?}\nconverted back: {}",
+                r.to_int(),
+                r.display_with(options)
+            ),
+            (Direction::ToRoman, Ok(r)) => format!("numeral: {}", r.display_with(options)),
+            (_, Err(Failure::Numeral(err))) if err.position().is_some() => {
+                format!("error: {}", err.render(&record.input))
+            }
+            (_, Err(err)) => format!("error: {err}"),
+        })
+    }
+}
+
+// completes the commands, and the modes after :mode
+struct Completions;
+
+impl Completer for Completions {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line.rfind([' ', ',']).map_or(0, |i| i + 1);
+        let candidates: Vec<&str> = if start == 0 {
+            COMMANDS.to_vec()
+        } else if line.starts_with(":mode ") {
+            MODES.concat()
+        } else {
+            Vec::new()
+        };
+        let word = &line[start..];
+        let candidates = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .map(String::from);
+        Ok((start, candidates.collect()))
+    }
+}
+
+impl Hinter for Completions {
+    type Hint = String;
+}
+
+impl Highlighter for Completions {}
+
+impl Validator for Completions {}
+
+impl Helper for Completions {}
+
+// where the history is kept between sessions, in each platform's usual place for app data
+fn history_path() -> Option<PathBuf> {
+    let home = || env::var_os("HOME").map(PathBuf::from);
+    let data = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".local").join("share")))
+    };
+    Some(data?.join("roman_numerals").join("history.txt"))
+}
+
+// the prompt for a terminal, until :quit, Ctrl-D or Ctrl-C
+pub(crate) fn run(modes: Modes) -> ExitCode {
+    let mut editor = match Editor::<Completions, DefaultHistory>::new() {
+        Ok(editor) => editor,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    editor.set_helper(Some(Completions));
+    let history = history_path();
+    if let Some(path) = &history {
+        // there's nothing to load the first time
+        let _ = editor.load_history(path);
+    }
+
+    let mut session = Session::new(modes);
+    let mut status = ExitCode::SUCCESS;
+    loop {
+        match editor.readline("input: ") {
+            Ok(line) => {
+                if !line.trim().is_empty() {
+                    let _ = editor.add_history_entry(line.as_str());
+                }
+                match session.reply(&line) {
+                    Reply::Print(reply) => println!("{reply}"),
+                    Reply::Nothing => {}
+                    Reply::Quit => break,
+                }
+            }
+            Err(ReadlineError::Eof | ReadlineError::Interrupted) => break,
+            Err(err) => {
+                eprintln!("error: {err}");
+                status = ExitCode::FAILURE;
+                break;
+            }
+        }
+    }
+
+    if let Some(path) = &history {
+        let saved = match path.parent() {
+            Some(dir) => fs::create_dir_all(dir).map_err(ReadlineError::from),
+            None => Ok(()),
+        }
+        .and_then(|()| editor.save_history(path));
+        if let Err(err) = saved {
+            eprintln!("couldn't save the history to {}: {err}", path.display());
+        }
+    }
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use rustyline::{completion::Completer, history::DefaultHistory, Context};
+
+    use super::{Completions, Modes, Reply, Session};
+    use crate::calc::Remainders;
+
+    fn replies(lines: &[&str]) -> Vec<Reply> {
+        let mut session = Session::new(Modes::new(Remainders::Classical));
+        lines.iter().map(|line| session.reply(line)).collect()
+    }
+
+    fn print(reply: &str) -> Reply {
+        Reply::Print(reply.to_string())
+    }
+
+    #[test]
+    fn converts_and_calculates() {
+        assert_eq!(
+            replies(&["XIV", " 1984 ", "", "X + V", "IIII"]),
+            [
+                print("value: 14\nconverted back: XIV"),
+                print("numeral: MCMLXXXIV"),
+                Reply::Nothing,
+                print("result: XV (15)"),
+                print("error: IIII\n   ^ 'I' repeated more than three times at position 3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn switches_modes() {
+        assert_eq!(
+            replies(&[
+                ":mode",
+                ":mode lowercase lenient",
+                "IIII",
+                ":mode vinculum twelfths",
+                ":mode strict vinculum twelfths",
+                "5000",
+                "X / III",
+                ":mode uppercase roman",
+                ":mode uppercase,strict",
+                ":mode lowercase,bogus",
+                ":mode lenient",
+                ":mode lenient standard",
+                ":mode",
+            ]),
+            [
+                print("modes: uppercase, strict, standard, remainders"),
+                print("modes: lowercase, lenient, standard, remainders"),
+                print("value: 4\nconverted back: iv"),
+                print("error: lenient and vinculum can't be used together"),
+                print("modes: lowercase, strict, vinculum, twelfths"),
+                print("numeral: v\u{305}"),
+                print("result: iii···· (3 4/12)"),
+                print("error: unknown mode \"roman\", see :help"),
+                print("modes: uppercase, strict, vinculum, twelfths"),
+                print("error: unknown mode \"bogus\", see :help"),
+                print("error: lenient and vinculum can't be used together"),
+                print("modes: uppercase, lenient, standard, twelfths"),
+                print("modes: uppercase, lenient, standard, twelfths"),
+            ]
+        );
+    }
+
+    #[test]
+    fn commands() {
+        let replies = replies(&[":help", ":quit", ":q", ":frobnicate"]);
+        assert!(matches!(&replies[0], Reply::Print(help) if help.contains(":mode <MODE>")));
+        assert_eq!(replies[1], Reply::Quit);
+        assert_eq!(replies[2], Reply::Quit);
+        assert_eq!(
+            replies[3],
+            print("error: unknown command \":frobnicate\", see :help")
+        );
+    }
+
+    #[test]
+    fn completes() {
+        let history = DefaultHistory::new();
+        let complete = |line: &str| {
+            Completions
+                .complete(line, line.len(), &Context::new(&history))
+                .unwrap()
+        };
+        assert_eq!(complete(":m"), (0, vec![":mode".to_string()]));
+        assert_eq!(
+            complete(":"),
+            (
+                0,
+                vec![
+                    ":help".to_string(),
+                    ":mode".to_string(),
+                    ":quit".to_string()
+                ]
+            )
+        );
+        assert_eq!(
+            complete(":mode strict l"),
+            (13, vec!["lowercase".to_string(), "lenient".to_string()])
+        );
+        assert_eq!(
+            complete(":mode lowercase,vin"),
+            (16, vec!["vinculum".to_string()])
+        );
+        assert_eq!(complete("X + I"), (4, Vec::new()));
+    }
+}
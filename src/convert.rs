@@ -0,0 +1,171 @@
+use core::num::{NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize};
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+
+use crate::{RomanNumeral, RomanNumeralError};
+
+// integers that always fit in a u32 and only have to be checked for zero and below
+macro_rules! try_from_small {
+    ($($int:ty),*) => {$(
+        impl TryFrom<$int> for RomanNumeral {
+            type Error = RomanNumeralError;
+            fn try_from(v: $int) -> Result<RomanNumeral, RomanNumeralError> {
+                RomanNumeral::from_i64(i64::from(v))
+            }
+        }
+    )*};
+}
+
+// integers that may be larger than a u32 as well
+macro_rules! try_from_large {
+    ($($int:ty),*) => {$(
+        impl TryFrom<$int> for RomanNumeral {
+            type Error = RomanNumeralError;
+            fn try_from(v: $int) -> Result<RomanNumeral, RomanNumeralError> {
+                if v < 1 {
+                    return Err(RomanNumeralError::NonPositiveNumeral);
+                }
+                let v = u32::try_from(v).map_err(|_| RomanNumeralError::Overflow)?;
+                RomanNumeral::with_value(v)
+            }
+        }
+    )*};
+}
+
+try_from_small!(u8, u16, u32, i8, i16, i32, i64);
+try_from_large!(u64, u128, usize, i128, isize);
+
+macro_rules! try_from_non_zero {
+    ($($non_zero:ty),*) => {$(
+        impl TryFrom<$non_zero> for RomanNumeral {
+            type Error = RomanNumeralError;
+            fn try_from(v: $non_zero) -> Result<RomanNumeral, RomanNumeralError> {
+                RomanNumeral::try_from(v.get())
+            }
+        }
+    )*};
+}
+
+try_from_non_zero!(
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+
+impl From<NonZeroU8> for RomanNumeral {
+    fn from(v: NonZeroU8) -> RomanNumeral {
+        RomanNumeral::new(v.into())
+    }
+}
+
+impl From<NonZeroU16> for RomanNumeral {
+    fn from(v: NonZeroU16) -> RomanNumeral {
+        RomanNumeral::new(v.into())
+    }
+}
+
+impl From<NonZeroU32> for RomanNumeral {
+    fn from(v: NonZeroU32) -> RomanNumeral {
+        RomanNumeral::new(v)
+    }
+}
+
+macro_rules! into_int {
+    ($($int:ty),*) => {$(
+        impl From<RomanNumeral> for $int {
+            fn from(n: RomanNumeral) -> $int {
+                n.to_int().into()
+            }
+        }
+    )*};
+}
+
+into_int!(u32, u64, u128, i64, i128);
+
+macro_rules! into_non_zero {
+    ($($non_zero:ty),*) => {$(
+        impl From<RomanNumeral> for $non_zero {
+            fn from(n: RomanNumeral) -> $non_zero {
+                n.get().into()
+            }
+        }
+    )*};
+}
+
+into_non_zero!(NonZeroU32, NonZeroU64, NonZeroU128, NonZeroI64, NonZeroI128);
+
+// integers that not every numeral fits in
+macro_rules! try_into_int {
+    ($($int:ty),*) => {$(
+        impl TryFrom<RomanNumeral> for $int {
+            type Error = RomanNumeralError;
+            fn try_from(n: RomanNumeral) -> Result<$int, RomanNumeralError> {
+                <$int>::try_from(n.to_int()).map_err(|_| RomanNumeralError::Overflow)
+            }
+        }
+    )*};
+}
+
+try_into_int!(u8, u16, usize, i8, i16, i32, isize);
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroI64, NonZeroU32, NonZeroU8};
+
+    use crate::{RomanNumeral, RomanNumeralError};
+
+    #[test]
+    fn from_integers() {
+        assert_eq!(RomanNumeral::try_from(12_u8).unwrap().to_int(), 12);
+        assert_eq!(RomanNumeral::try_from(1984_i64).unwrap().to_int(), 1984);
+        assert_eq!(
+            RomanNumeral::try_from(4_000_000_000_usize)
+                .unwrap()
+                .to_int(),
+            4_000_000_000
+        );
+        assert_eq!(
+            RomanNumeral::try_from(0_u64).unwrap_err(),
+            RomanNumeralError::NonPositiveNumeral
+        );
+        assert_eq!(
+            RomanNumeral::try_from(-5_i32).unwrap_err(),
+            RomanNumeralError::NonPositiveNumeral
+        );
+        assert_eq!(
+            RomanNumeral::try_from(i128::MIN).unwrap_err(),
+            RomanNumeralError::NonPositiveNumeral
+        );
+        assert_eq!(
+            RomanNumeral::try_from(u32::MAX as u64 + 1).unwrap_err(),
+            RomanNumeralError::Overflow
+        );
+        assert_eq!(
+            RomanNumeral::try_from(NonZeroI64::new(-1).unwrap()).unwrap_err(),
+            RomanNumeralError::NonPositiveNumeral
+        );
+        assert_eq!(RomanNumeral::from(NonZeroU8::MAX).to_int(), 255);
+    }
+
+    #[test]
+    fn into_integers() {
+        let n = RomanNumeral::with_value(300).unwrap();
+        assert_eq!(i64::from(n), 300);
+        assert_eq!(NonZeroU32::from(n).get(), 300);
+        assert_eq!(u8::try_from(n).unwrap_err(), RomanNumeralError::Overflow);
+        assert_eq!(u16::try_from(n).unwrap(), 300);
+    }
+
+    #[test]
+    fn niche() {
+        assert_eq!(
+            std::mem::size_of::<Option<RomanNumeral>>(),
+            std::mem::size_of::<u32>()
+        );
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+use core::fmt::Display;
+
+#[cfg(feature = "alloc")]
+use crate::format::is_combining_mark;
+
+/// Where in the input a character was found, both as a byte offset (for slicing) and as
+/// a char index (for pointing at it on screen).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// The offset into the input in bytes, where the character starts.
+    pub byte: usize,
+    /// The index of the character, counted in `char`s from the start of the input.
+    pub char: usize,
+}
+
+/// Everything that can go wrong when reading or writing a roman numeral.
+///
+/// Parse errors carry the [`Position`] of the character that broke the rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RomanNumeralError {
+    /// Zero and negative numbers have no roman numeral.
+    NonPositiveNumeral,
+    /// There was nothing to parse.
+    EmptyInput,
+    /// The character is not a roman numeral symbol.
+    InvalidChar(char, Position),
+    /// Only I, X and C may be subtracted, and only from the next two larger symbols, like
+    /// IV, IX, XL, XC, CD and CM.
+    IllegalSubtractivePair(char, char, Position),
+    /// V, L and D may appear at most once.
+    RepeatedSymbol(char, Position),
+    /// I, X and C may be repeated at most three times in a row.
+    TooManyRepeats(char, Position),
+    /// Symbols have to go from largest to smallest, with at most one subtractive pair or
+    /// run of symbols per decimal place.
+    OutOfOrder(char, Position),
+    /// At most five unciae may follow the whole part of a
+    /// [`RomanFraction`](crate::RomanFraction), six are written S.
+    TooManyUnciae(char, Position),
+    /// The buffer given to [`RomanNumeral::to_roman_buf`](crate::RomanNumeral::to_roman_buf)
+    /// can't fit the numeral.
+    BufferTooSmall,
+    /// The number is larger than the largest [`RomanNumeral`](crate::RomanNumeral), which
+    /// is [`u32::MAX`].
+    Overflow,
+}
+
+impl RomanNumeralError {
+    /// The position of the offending character, if the error came from parsing a
+    /// specific character.
+    pub const fn position(&self) -> Option<Position> {
+        match *self {
+            RomanNumeralError::NonPositiveNumeral
+            | RomanNumeralError::EmptyInput
+            | RomanNumeralError::BufferTooSmall
+            | RomanNumeralError::Overflow => None,
+            RomanNumeralError::InvalidChar(_, pos)
+            | RomanNumeralError::IllegalSubtractivePair(_, _, pos)
+            | RomanNumeralError::RepeatedSymbol(_, pos)
+            | RomanNumeralError::TooManyRepeats(_, pos)
+            | RomanNumeralError::OutOfOrder(_, pos)
+            | RomanNumeralError::TooManyUnciae(_, pos) => Some(pos),
+        }
+    }
+
+    /// Which rule was broken, without any of the details.
+    pub const fn rule(&self) -> &'static str {
+        match self {
+            RomanNumeralError::NonPositiveNumeral => {
+                "roman numerals can only represent positive numbers"
+            }
+            RomanNumeralError::EmptyInput => "empty string",
+            RomanNumeralError::InvalidChar(..) => "invalid character",
+            RomanNumeralError::IllegalSubtractivePair(..) => {
+                "only I, X and C may be subtracted, and only from the next two larger symbols"
+            }
+            RomanNumeralError::RepeatedSymbol(..) => "V, L and D may appear at most once",
+            RomanNumeralError::TooManyRepeats(..) => {
+                "I, X and C may be repeated at most three times in a row"
+            }
+            RomanNumeralError::OutOfOrder(..) => "symbols have to go from largest to smallest",
+            RomanNumeralError::TooManyUnciae(..) => "at most five unciae may follow, six are S",
+            RomanNumeralError::BufferTooSmall => "buffer too small for the numeral",
+            RomanNumeralError::Overflow => "the number is too large to be represented",
+        }
+    }
+
+    /// Renders `input` on one line and a caret under the offending character on the next,
+    /// followed by the error message. Combining marks like the vinculum's overlines don't
+    /// take up a column of their own.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let err = RomanNumeral::from_str("MCMC").unwrap_err();
+    /// assert_eq!(err.render("MCMC"), "MCMC\n   ^ 'C' is out of order at position 3");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn render(&self, input: &str) -> String {
+        match self.position() {
+            Some(pos) => {
+                let column = input.get(..pos.byte).map_or(pos.char, |before| {
+                    before.chars().filter(|&c| !is_combining_mark(c)).count()
+                });
+                format!("{input}\n{:>width$} {self}", "^", width = column + 1)
+            }
+            None => format!("{input}\n{self}"),
+        }
+    }
+}
+
+impl Display for RomanNumeralError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RomanNumeralError::NonPositiveNumeral => {
+                write!(f, "roman numerals can only represent positive numbers")
+            }
+            RomanNumeralError::EmptyInput => write!(f, "empty string"),
+            RomanNumeralError::InvalidChar(c, pos) => {
+                write!(f, "invalid character {c:?} at position {}", pos.char)
+            }
+            RomanNumeralError::IllegalSubtractivePair(a, b, pos) => write!(
+                f,
+                "{a:?} cannot be subtracted from {b:?} at position {}",
+                pos.char
+            ),
+            RomanNumeralError::RepeatedSymbol(c, pos) => {
+                write!(f, "{c:?} cannot be repeated at position {}", pos.char)
+            }
+            RomanNumeralError::TooManyRepeats(c, pos) => write!(
+                f,
+                "{c:?} repeated more than three times at position {}",
+                pos.char
+            ),
+            RomanNumeralError::OutOfOrder(c, pos) => {
+                write!(f, "{c:?} is out of order at position {}", pos.char)
+            }
+            RomanNumeralError::TooManyUnciae(c, pos) => {
+                write!(
+                    f,
+                    "{c:?} makes more than five unciae at position {}",
+                    pos.char
+                )
+            }
+            RomanNumeralError::BufferTooSmall | RomanNumeralError::Overflow => {
+                write!(f, "{}", self.rule())
+            }
+        }
+    }
+}
+
+impl core::error::Error for RomanNumeralError {}
@@ -0,0 +1,255 @@
+use core::{
+    fmt::{self, Display},
+    iter::FusedIterator,
+    str::FromStr,
+};
+
+use crate::{
+    greatest_str_leq_than_n,
+    parse::{plain_symbols, PlainSymbols, Symbol},
+    Position, RomanNumeral, RomanNumeralError,
+};
+
+/// One step of writing a numeral, see [`RomanNumeral::format_steps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatStep {
+    /// The symbols written, like "CM".
+    pub symbols: &'static str,
+    /// What they're worth.
+    pub value: u32,
+    /// What is left to write after them.
+    pub remaining: u32,
+}
+
+impl Display for FormatStep {
+    /// Writes the step like "CM = 900, leaving 84".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} = {}, leaving {}",
+            self.symbols, self.value, self.remaining
+        )
+    }
+}
+
+/// The steps of writing a numeral, see [`RomanNumeral::format_steps`].
+#[derive(Debug, Clone)]
+pub struct FormatSteps {
+    remaining: i64,
+}
+
+impl Iterator for FormatSteps {
+    type Item = FormatStep;
+
+    fn next(&mut self) -> Option<FormatStep> {
+        let (symbols, value) = greatest_str_leq_than_n(self.remaining);
+        if value == 0 {
+            return None;
+        }
+        self.remaining -= value;
+        Some(FormatStep {
+            symbols,
+            value: value as u32,
+            remaining: self.remaining as u32,
+        })
+    }
+}
+
+impl FusedIterator for FormatSteps {}
+
+/// Whether a symbol counts towards the total or against it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// The symbol is followed by one of no greater value, so it is added.
+    Add,
+    /// The symbol is followed by a greater one, like the C in CM, so it is subtracted.
+    Subtract,
+}
+
+/// One symbol of a numeral being read, see [`RomanNumeral::parse_steps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseStep {
+    /// The symbol as written.
+    pub symbol: char,
+    /// Where it was found.
+    pub position: Position,
+    /// What it's worth on its own.
+    pub value: u32,
+    /// Whether it was added or subtracted.
+    pub operation: Operation,
+    /// The running total after this symbol, which is negative while reading a numeral
+    /// starting with a subtraction.
+    pub total: i64,
+}
+
+impl Display for ParseStep {
+    /// Writes the step like "subtract C (100), total 900".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operation = match self.operation {
+            Operation::Add => "add",
+            Operation::Subtract => "subtract",
+        };
+        write!(
+            f,
+            "{operation} {} ({}), total {}",
+            self.symbol, self.value, self.total
+        )
+    }
+}
+
+/// The steps of reading a numeral, see [`RomanNumeral::parse_steps`].
+#[derive(Clone)]
+pub struct ParseSteps<'a> {
+    symbols: PlainSymbols<'a>,
+    current: Option<Symbol>,
+    total: i64,
+}
+
+impl ParseSteps<'_> {
+    // the input has already been parsed, so reading it again can't fail
+    fn next_symbol(&mut self) -> Option<Symbol> {
+        self.symbols
+            .next()
+            .map(|symbol| symbol.expect("the numeral has been checked"))
+    }
+}
+
+impl fmt::Debug for ParseSteps<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParseSteps")
+            .field("total", &self.total)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for ParseSteps<'_> {
+    type Item = ParseStep;
+
+    fn next(&mut self) -> Option<ParseStep> {
+        let current = self.current.take()?;
+        self.current = self.next_symbol();
+        let operation = match self.current {
+            Some(next) if current.value < next.value => Operation::Subtract,
+            _ => Operation::Add,
+        };
+        self.total += match operation {
+            Operation::Add => current.value,
+            Operation::Subtract => -current.value,
+        };
+        Some(ParseStep {
+            symbol: current.c,
+            position: current.pos,
+            value: current.value as u32,
+            operation,
+            total: self.total,
+        })
+    }
+}
+
+impl FusedIterator for ParseSteps<'_> {}
+
+impl RomanNumeral {
+    /// How [`RomanNumeral::to_roman_numeral_string`] writes the numeral: the greatest
+    /// symbol or subtractive pair that fits is written, and the rest of the value is
+    /// written the same way.
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let steps: Vec<_> = RomanNumeral::with_value(1984)?
+    ///     .format_steps()
+    ///     .map(|step| step.to_string())
+    ///     .collect();
+    /// assert_eq!(
+    ///     steps,
+    ///     [
+    ///         "M = 1000, leaving 984",
+    ///         "CM = 900, leaving 84",
+    ///         "L = 50, leaving 34",
+    ///         "X = 10, leaving 24",
+    ///         "X = 10, leaving 14",
+    ///         "X = 10, leaving 4",
+    ///         "IV = 4, leaving 0",
+    ///     ]
+    /// );
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub fn format_steps(&self) -> FormatSteps {
+        FormatSteps {
+            remaining: self.value(),
+        }
+    }
+
+    /// How [`FromStr`] reads `s`: each symbol is added, unless the
+    /// next one is greater and it is subtracted instead. Fails like
+    /// [`FromStr`] if `s` isn't a canonical numeral.
+    ///
+    /// ```
+    /// use roman_numerals::{Operation, RomanNumeral};
+    ///
+    /// let steps: Vec<_> = RomanNumeral::parse_steps("XIV")?.collect();
+    /// assert_eq!(steps[1].operation, Operation::Subtract);
+    /// assert_eq!(steps[1].total, 9);
+    /// assert_eq!(steps[2].to_string(), "add V (5), total 14");
+    /// assert!(RomanNumeral::parse_steps("IIII").is_err());
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub fn parse_steps(s: &str) -> Result<ParseSteps<'_>, RomanNumeralError> {
+        RomanNumeral::from_str(s)?;
+        let mut steps = ParseSteps {
+            symbols: plain_symbols(s),
+            current: None,
+            total: 0,
+        };
+        steps.current = steps.next_symbol();
+        Ok(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use std::string::String;
+    use std::vec::Vec;
+
+    use crate::{Operation, RomanNumeral, RomanNumeralError};
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn format_steps_write_the_numeral() {
+        for v in 1..2_000 {
+            let n = RomanNumeral::with_value(v).unwrap();
+            let written: String = n.format_steps().map(|step| step.symbols).collect();
+            assert_eq!(written, n.to_roman_numeral_string());
+            assert_eq!(n.format_steps().last().unwrap().remaining, 0);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_steps_add_up() {
+        for v in 1..2_000 {
+            let s = RomanNumeral::with_value(v)
+                .unwrap()
+                .to_roman_numeral_string();
+            let last = RomanNumeral::parse_steps(&s).unwrap().last().unwrap();
+            assert_eq!(last.total, i64::from(v));
+        }
+    }
+
+    #[test]
+    fn parse_steps() {
+        let steps: Vec<_> = RomanNumeral::parse_steps("cm").unwrap().collect();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(
+            (steps[0].symbol, steps[0].operation, steps[0].total),
+            ('c', Operation::Subtract, -100)
+        );
+        assert_eq!(steps[1].position.char, 1);
+        assert_eq!(steps[1].total, 900);
+        assert_eq!(
+            RomanNumeral::parse_steps("MCMC").unwrap_err(),
+            RomanNumeralError::OutOfOrder('C', crate::Position { byte: 3, char: 3 })
+        );
+    }
+}
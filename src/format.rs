@@ -0,0 +1,533 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{
+    fmt::{self, Display, Write},
+    ops::Deref,
+};
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::{
+    greatest_str_leq_than_n, vinculum::write_vinculum, RomanNumeral, RomanNumeralError,
+    VinculumStyle,
+};
+
+/// The longest numeral [`RomanNumeral::to_roman_numeral_string`] writes for anything from 1
+/// to 3999, which is MMMDCCCLXXXVIII for 3888. Other styles can be longer.
+pub const MAX_CANONICAL_LEN: usize = 15;
+
+/// A numeral of up to [`MAX_CANONICAL_LEN`] bytes stored inline, as written by the const
+/// [`RomanNumeral::to_roman_str`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RomanStr {
+    bytes: [u8; MAX_CANONICAL_LEN],
+    len: usize,
+}
+
+impl RomanStr {
+    pub(crate) const fn new() -> RomanStr {
+        RomanStr {
+            bytes: [0; MAX_CANONICAL_LEN],
+            len: 0,
+        }
+    }
+
+    // appends `s` if it fits, returning whether it did
+    pub(crate) const fn push_str(&mut self, s: &str) -> bool {
+        let s = s.as_bytes();
+        if self.len + s.len() > MAX_CANONICAL_LEN {
+            return false;
+        }
+        let mut i = 0;
+        while i < s.len() {
+            self.bytes[self.len + i] = s[i];
+            i += 1;
+        }
+        self.len += s.len();
+        true
+    }
+
+    /// The numeral.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).expect("only whole strs are pushed")
+    }
+}
+
+impl Deref for RomanStr {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for RomanStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for RomanStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Which combinations of symbols [`FormatOptions`] writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumeralStyle {
+    /// IV, IX, XL and so on, like [`RomanNumeral::to_roman_numeral_string`].
+    #[default]
+    Subtractive,
+    /// Only ever adds symbols, so 4 is IIII, 9 is VIIII and 900 is DCCCC.
+    Additive,
+    /// Subtractive except for a 4 in the ones, which is IIII like on clock faces.
+    ClockFace,
+}
+
+/// How [`RomanNumeral::to_roman_numeral_string_with`] writes a numeral.
+///
+/// The options are a small `Copy` value that is built up with chained calls, so a style can be
+/// picked per call or kept around in a `const`.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use roman_numerals::{FormatOptions, NumeralStyle, RomanNumeral};
+///
+/// const MEDIEVAL: FormatOptions = FormatOptions::new().lowercase(true).j_terminal(true);
+///
+/// let n = RomanNumeral::with_value(8).unwrap();
+/// assert_eq!(n.to_roman_numeral_string_with(MEDIEVAL), "viij");
+/// let clock = FormatOptions::new().style(NumeralStyle::ClockFace);
+/// assert_eq!(RomanNumeral::with_value(4).unwrap().to_roman_numeral_string_with(clock), "IIII");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FormatOptions {
+    style: NumeralStyle,
+    lowercase: bool,
+    j_terminal: bool,
+    vinculum: Option<VinculumStyle>,
+}
+
+impl FormatOptions {
+    /// Uppercase subtractive notation, the same as [`RomanNumeral::to_roman_numeral_string`].
+    pub const fn new() -> FormatOptions {
+        FormatOptions {
+            style: NumeralStyle::Subtractive,
+            lowercase: false,
+            j_terminal: false,
+            vinculum: None,
+        }
+    }
+
+    /// Which combinations of symbols to write.
+    pub const fn style(mut self, style: NumeralStyle) -> FormatOptions {
+        self.style = style;
+        self
+    }
+
+    /// Write lowercase letters, like "xiv".
+    pub const fn lowercase(mut self, lowercase: bool) -> FormatOptions {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// Write the last I of a numeral as J, like the "viij" of medieval manuscripts.
+    pub const fn j_terminal(mut self, j_terminal: bool) -> FormatOptions {
+        self.j_terminal = j_terminal;
+        self
+    }
+
+    /// Write numbers from 4000 on in vinculum notation, see
+    /// [`RomanNumeral::to_vinculum_string`].
+    pub const fn vinculum(mut self, vinculum: Option<VinculumStyle>) -> FormatOptions {
+        self.vinculum = vinculum;
+        self
+    }
+
+    /// Whether lowercase letters are written, see [`FormatOptions::lowercase`].
+    pub const fn is_lowercase(&self) -> bool {
+        self.lowercase
+    }
+
+    // writes the numeral for `val` as a plain run of letters, nothing at all for 0
+    pub(crate) fn write_plain(
+        &self,
+        w: &mut dyn fmt::Write,
+        mut val: i64,
+        terminal: bool,
+    ) -> fmt::Result {
+        while val > 0 {
+            let (s, v) = match self.style {
+                NumeralStyle::Subtractive => greatest_str_leq_than_n(val),
+                NumeralStyle::Additive => greatest_additive_str_leq_than_n(val),
+                NumeralStyle::ClockFace if val == 4 => ("IIII", 4),
+                NumeralStyle::ClockFace => greatest_str_leq_than_n(val),
+            };
+            val -= v;
+            for (i, c) in s.char_indices() {
+                let last = terminal && val == 0 && i == s.len() - 1;
+                let c = if self.j_terminal && last && c == 'I' {
+                    'J'
+                } else {
+                    c
+                };
+                w.write_char(if self.lowercase {
+                    c.to_ascii_lowercase()
+                } else {
+                    c
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn greatest_additive_str_leq_than_n(v: i64) -> (&'static str, i64) {
+    match v {
+        1000.. => ("M", 1000),
+        500.. => ("D", 500),
+        100.. => ("C", 100),
+        50.. => ("L", 50),
+        10.. => ("X", 10),
+        5.. => ("V", 5),
+        1.. => ("I", 1),
+        _ => ("", 0),
+    }
+}
+
+/// Displays a [`RomanNumeral`] as described by [`FormatOptions`], see
+/// [`RomanNumeral::display_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayWith<'a> {
+    numeral: &'a RomanNumeral,
+    options: FormatOptions,
+}
+
+// `{:#}` asks for lowercase on top of the options
+impl Display for DisplayWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = self
+            .options
+            .lowercase(self.options.lowercase || f.alternate());
+        pad(f, |mut w| self.numeral.write_roman_with(&mut w, options))
+    }
+}
+
+// does what `Formatter::pad` does with whatever `write` writes, without collecting it into a
+// string first
+pub(crate) fn pad(
+    f: &mut fmt::Formatter<'_>,
+    write: impl Fn(&mut dyn Write) -> fmt::Result,
+) -> fmt::Result {
+    if f.width().is_none() && f.precision().is_none() {
+        // nothing to pad or cut off, so the numeral can go straight to the formatter
+        return write(f);
+    }
+
+    let mut counter = CharCounter(0);
+    write(&mut counter)?;
+    let len = f
+        .precision()
+        .map_or(counter.0, |precision| counter.0.min(precision));
+    let padding = f.width().unwrap_or(0).saturating_sub(len);
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(fmt::Alignment::Left) | None => (0, padding),
+    };
+
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    write(&mut Truncated {
+        inner: f,
+        remaining: len,
+        marking: false,
+    })?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+// marks like the vinculum's overlines that belong to the char before them, so they don't
+// take up a column of their own
+pub(crate) const fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{300}'..='\u{36f}')
+}
+
+// counts the symbols written to it, which is what width and precision are measured in, each
+// being a char along with any combining marks on it
+struct CharCounter(usize);
+
+impl Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().filter(|&c| !is_combining_mark(c)).count();
+        Ok(())
+    }
+}
+
+// passes on the first `remaining` symbols and drops the rest, keeping the marks on the last
+// one so V̅ isn't cut down to V
+struct Truncated<'a> {
+    inner: &'a mut dyn Write,
+    remaining: usize,
+    // whether the symbol the next combining mark belongs to was passed on
+    marking: bool,
+}
+
+impl Write for Truncated<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if is_combining_mark(c) {
+                if self.marking {
+                    self.inner.write_char(c)?;
+                }
+                continue;
+            }
+            self.marking = self.remaining > 0;
+            if !self.marking {
+                break;
+            }
+            self.inner.write_char(c)?;
+            self.remaining -= 1;
+        }
+        Ok(())
+    }
+}
+
+impl RomanNumeral {
+    /// Something to put in `format!` and friends that writes the numeral as described by
+    /// `options`, honoring fill, width, alignment and precision. `{:#}` writes it in lowercase.
+    ///
+    /// ```
+    /// use roman_numerals::{FormatOptions, NumeralStyle, RomanNumeral};
+    ///
+    /// let clock = FormatOptions::new().style(NumeralStyle::ClockFace);
+    /// let n = RomanNumeral::with_value(4).unwrap();
+    /// assert_eq!(format!("[{:>6}]", n.display_with(clock)), "[  IIII]");
+    /// assert_eq!(format!("[{:<#6}]", n.display_with(clock)), "[iiii  ]");
+    /// ```
+    pub fn display_with(&self, options: FormatOptions) -> DisplayWith<'_> {
+        DisplayWith {
+            numeral: self,
+            options,
+        }
+    }
+
+    /// Writes the numeral as described by `options`.
+    ///
+    /// ```
+    /// use roman_numerals::{FormatOptions, NumeralStyle, RomanNumeral};
+    ///
+    /// let additive = FormatOptions::new().style(NumeralStyle::Additive);
+    /// let n = RomanNumeral::with_value(1999).unwrap();
+    /// assert_eq!(n.to_roman_numeral_string_with(additive), "MDCCCCLXXXXVIIII");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_roman_numeral_string_with(&self, options: FormatOptions) -> String {
+        let mut result = String::new();
+        self.write_roman_with(&mut result, options)
+            .expect("writing to a String never fails");
+        result
+    }
+
+    /// Writes the numeral like [`RomanNumeral::to_roman_numeral_string`] without allocating.
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let mut page = String::from("page ");
+    /// RomanNumeral::with_value(14).unwrap().write_roman(&mut page).unwrap();
+    /// assert_eq!(page, "page XIV");
+    /// ```
+    pub fn write_roman(&self, w: &mut impl Write) -> fmt::Result {
+        self.write_roman_with(w, FormatOptions::new())
+    }
+
+    /// Writes the numeral as described by `options` without allocating.
+    pub fn write_roman_with(&self, w: &mut impl Write, options: FormatOptions) -> fmt::Result {
+        match options.vinculum {
+            Some(vinculum) if self.value() >= 4000 => {
+                write_vinculum(w, self.value(), vinculum, |w, group, level| {
+                    options.write_plain(w, group, level == 0)
+                })
+            }
+            _ => options.write_plain(w, self.value(), true),
+        }
+    }
+
+    /// Writes the numeral as described by `options` to an [`io::Write`].
+    ///
+    /// ```
+    /// use roman_numerals::{FormatOptions, RomanNumeral};
+    ///
+    /// let mut out = Vec::new();
+    /// RomanNumeral::with_value(9).unwrap().write_roman_io(&mut out, FormatOptions::new()).unwrap();
+    /// assert_eq!(out, b"IX");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_roman_io(&self, w: &mut impl io::Write, options: FormatOptions) -> io::Result<()> {
+        write!(w, "{}", self.display_with(options))
+    }
+
+    /// Writes the numeral into `buf` and returns the part of it that was written to, failing
+    /// with [`RomanNumeralError::BufferTooSmall`] if it doesn't fit. A buffer of
+    /// [`MAX_CANONICAL_LEN`] bytes fits any numeral up to 3999 in subtractive notation.
+    ///
+    /// ```
+    /// use roman_numerals::{FormatOptions, RomanNumeral, MAX_CANONICAL_LEN};
+    ///
+    /// let mut buf = [0; MAX_CANONICAL_LEN];
+    /// let s = RomanNumeral::with_value(3888).unwrap().to_roman_buf(&mut buf, FormatOptions::new());
+    /// assert_eq!(s.unwrap(), "MMMDCCCLXXXVIII");
+    /// ```
+    pub fn to_roman_buf<'b>(
+        &self,
+        buf: &'b mut [u8],
+        options: FormatOptions,
+    ) -> Result<&'b str, RomanNumeralError> {
+        let mut writer = SliceWriter { buf, len: 0 };
+        self.write_roman_with(&mut writer, options)
+            .map_err(|_| RomanNumeralError::BufferTooSmall)?;
+        let SliceWriter { buf, len } = writer;
+        Ok(core::str::from_utf8(&buf[..len]).expect("only whole strs are written"))
+    }
+}
+
+// fills a byte slice from the start, failing rather than writing part of a str
+struct SliceWriter<'b> {
+    buf: &'b mut [u8],
+    len: usize,
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use std::string::String;
+    use std::{format, string::ToString};
+
+    use crate::{
+        FormatOptions, NumeralStyle, RomanNumeral, RomanNumeralError, VinculumStyle,
+        MAX_CANONICAL_LEN,
+    };
+
+    #[cfg(feature = "alloc")]
+    fn format(v: u32, options: FormatOptions) -> String {
+        RomanNumeral::with_value(v)
+            .unwrap()
+            .to_roman_numeral_string_with(options)
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn default_matches_to_roman_numeral_string() {
+        for i in 1..5_000 {
+            assert_eq!(
+                format(i, FormatOptions::new()),
+                RomanNumeral::with_value(i)
+                    .unwrap()
+                    .to_roman_numeral_string()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn styles() {
+        let additive = FormatOptions::new().style(NumeralStyle::Additive);
+        assert_eq!(format(4, additive), "IIII");
+        assert_eq!(format(9, additive), "VIIII");
+        assert_eq!(format(900, additive), "DCCCC");
+
+        let clock = FormatOptions::new().style(NumeralStyle::ClockFace);
+        assert_eq!(format(4, clock), "IIII");
+        assert_eq!(format(9, clock), "IX");
+        assert_eq!(format(14, clock), "XIIII");
+        assert_eq!(format(40, clock), "XL");
+
+        let lower = FormatOptions::new().lowercase(true);
+        assert_eq!(format(1984, lower), "mcmlxxxiv");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn j_terminal() {
+        let medieval = FormatOptions::new().lowercase(true).j_terminal(true);
+        assert_eq!(format(1, medieval), "j");
+        assert_eq!(format(8, medieval), "viij");
+        assert_eq!(format(4, medieval), "iv");
+        assert_eq!(format(10, medieval), "x");
+        assert_eq!(format(4, medieval.style(NumeralStyle::Additive)), "iiij");
+        assert_eq!(format(3, medieval.lowercase(false)), "IIJ");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn vinculum() {
+        let options = FormatOptions::new()
+            .vinculum(Some(VinculumStyle::Underscore))
+            .style(NumeralStyle::Additive)
+            .lowercase(true)
+            .j_terminal(true);
+        assert_eq!(format(3_999, options), "mmmdcccclxxxxviiij");
+        assert_eq!(format(4_001, options), "_i_i_i_ij");
+    }
+
+    #[test]
+    fn padding_counts_symbols() {
+        let n = RomanNumeral::with_value(5_001).unwrap();
+        let options = FormatOptions::new().vinculum(Some(VinculumStyle::Unicode));
+        // the overline goes along with the V, both when counting and when cutting off
+        assert_eq!(
+            format!("[{:>5}]", n.display_with(options)),
+            "[   V\u{305}I]"
+        );
+        assert_eq!(
+            format!("[{:^7.2}]", n.display_with(options)),
+            "[  V\u{305}I   ]"
+        );
+        assert_eq!(
+            format!("[{:-<4.1}]", n.display_with(options)),
+            "[V\u{305}---]"
+        );
+        let n = RomanNumeral::with_value(501_001).unwrap();
+        assert_eq!(
+            format!("{:.2}", n.display_with(options)),
+            "V\u{33f}I\u{305}"
+        );
+    }
+
+    #[test]
+    fn write_without_allocating() {
+        let mut buf = [0; MAX_CANONICAL_LEN];
+        for i in 1..4_000 {
+            let n = RomanNumeral::with_value(i).unwrap();
+            assert_eq!(
+                n.to_roman_buf(&mut buf, FormatOptions::new()).unwrap(),
+                n.to_string()
+            );
+        }
+        let additive = FormatOptions::new().style(NumeralStyle::Additive);
+        assert_eq!(
+            RomanNumeral::with_value(3_999)
+                .unwrap()
+                .to_roman_buf(&mut buf, additive),
+            Err(RomanNumeralError::BufferTooSmall)
+        );
+    }
+}
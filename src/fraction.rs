@@ -0,0 +1,464 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{
+    fmt::{self, Display, Write},
+    num::NonZeroU64,
+    ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::{
+    format::pad, parse::char_to_value, FormatOptions, Position, RomanNumeral, RomanNumeralError,
+};
+
+// the largest value, u32::MAX and 23/24
+const MAX_TWENTY_FOURTHS: u64 = (u32::MAX as u64 + 1) * 24 - 1;
+
+// what a fraction sign is worth in twenty-fourths, `None` if it isn't one
+const fn sign_value(c: char) -> Option<u64> {
+    match c {
+        // the semis
+        'S' | 's' => Some(12),
+        // the uncia, with a full stop for ASCII
+        '·' | '.' => Some(2),
+        // the quadrans, three unciae
+        '∴' => Some(6),
+        // the semuncia, with E for ASCII
+        'Ɛ' | 'ɛ' | 'E' | 'e' => Some(1),
+        _ => None,
+    }
+}
+
+/// A number of twelfths the way Roman accounts wrote them, with S for a half, a dot for each
+/// twelfth (an uncia) and Ɛ for a twenty-fourth (a semuncia) after the whole part, like
+/// "XIIS··" for 12 and 8/12. The value is always greater than zero and at most
+/// [`u32::MAX`] and 23/24.
+///
+/// Fractions compare and sort by their value.
+///
+/// ```
+/// use std::str::FromStr;
+/// use roman_numerals::RomanFraction;
+///
+/// let price = RomanFraction::from_str("XIIS··")?;
+/// assert_eq!((price.whole(), price.twelfths()), (12, 8));
+/// assert_eq!(price.to_rational(), (38, 3));
+/// assert_eq!(price.to_string(), "XIIS··");
+/// # Ok::<(), roman_numerals::RomanNumeralError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RomanFraction {
+    twenty_fourths: NonZeroU64,
+}
+
+impl RomanFraction {
+    /// `whole` and `twelfths` twelfths, with every twelve twelfths carried over to the
+    /// whole part. Fails with [`RomanNumeralError::NonPositiveNumeral`] if both are 0 and
+    /// with [`RomanNumeralError::Overflow`] if the whole part ends up too large.
+    ///
+    /// ```
+    /// use roman_numerals::RomanFraction;
+    ///
+    /// assert_eq!(RomanFraction::new(7, 15)?.to_string(), "VIII···");
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub const fn new(whole: u32, twelfths: u32) -> Result<RomanFraction, RomanNumeralError> {
+        RomanFraction::from_twenty_fourths(whole as u64 * 24 + twelfths as u64 * 2)
+    }
+
+    /// A fraction of `n` twenty-fourths, the smallest part written, failing like
+    /// [`RomanFraction::new`].
+    pub const fn from_twenty_fourths(n: u64) -> Result<RomanFraction, RomanNumeralError> {
+        if n > MAX_TWENTY_FOURTHS {
+            return Err(RomanNumeralError::Overflow);
+        }
+        match NonZeroU64::new(n) {
+            Some(twenty_fourths) => Ok(RomanFraction { twenty_fourths }),
+            None => Err(RomanNumeralError::NonPositiveNumeral),
+        }
+    }
+
+    /// The whole part, which is 0 for fractions less than one.
+    pub const fn whole(&self) -> u32 {
+        (self.twenty_fourths.get() / 24) as u32
+    }
+
+    /// The whole part as a numeral, if there is one.
+    pub const fn whole_numeral(&self) -> Option<RomanNumeral> {
+        match RomanNumeral::with_value(self.whole()) {
+            Ok(n) => Some(n),
+            Err(_) => None,
+        }
+    }
+
+    /// The twelfths after the whole part, from 0 to 11, not counting a semuncia.
+    pub const fn twelfths(&self) -> u32 {
+        (self.twenty_fourths.get() % 24 / 2) as u32
+    }
+
+    /// Whether there is a twenty-fourth left after the twelfths.
+    pub const fn has_semuncia(&self) -> bool {
+        self.twenty_fourths.get() % 2 == 1
+    }
+
+    /// The whole value in twenty-fourths.
+    pub const fn as_twenty_fourths(&self) -> u64 {
+        self.twenty_fourths.get()
+    }
+
+    /// The exact value as a numerator and a denominator in lowest terms.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use roman_numerals::RomanFraction;
+    ///
+    /// assert_eq!(RomanFraction::from_str("IIS")?.to_rational(), (5, 2));
+    /// assert_eq!(RomanFraction::from_str("X")?.to_rational(), (10, 1));
+    /// assert_eq!(RomanFraction::from_str("·Ɛ")?.to_rational(), (1, 8));
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub const fn to_rational(&self) -> (u64, u64) {
+        let n = self.twenty_fourths.get();
+        // the greatest common divisor with 24, by Euclid's algorithm
+        let (mut gcd, mut rest) = (n, 24);
+        while rest != 0 {
+            (gcd, rest) = (rest, gcd % rest);
+        }
+        (n / gcd, 24 / gcd)
+    }
+
+    // applies `op` to the twenty-fourths of two fractions
+    fn checked_op(
+        self,
+        rhs: u64,
+        op: fn(u64, u64) -> Option<u64>,
+        underflow: RomanNumeralError,
+    ) -> Result<RomanFraction, RomanNumeralError> {
+        match op(self.as_twenty_fourths(), rhs) {
+            Some(n) => RomanFraction::from_twenty_fourths(n),
+            None => Err(underflow),
+        }
+    }
+
+    /// Adds two fractions, failing with [`RomanNumeralError::Overflow`] if the sum is too
+    /// large.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use roman_numerals::RomanFraction;
+    ///
+    /// let sum = RomanFraction::from_str("IIS····")?.checked_add(RomanFraction::from_str("S··")?);
+    /// assert_eq!(sum?.to_string(), "IIIS");
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub fn checked_add(self, rhs: RomanFraction) -> Result<RomanFraction, RomanNumeralError> {
+        let overflow = RomanNumeralError::Overflow;
+        self.checked_op(rhs.as_twenty_fourths(), u64::checked_add, overflow)
+    }
+
+    /// Subtracts `rhs`, failing with [`RomanNumeralError::NonPositiveNumeral`] unless it's
+    /// smaller than `self`.
+    pub fn checked_sub(self, rhs: RomanFraction) -> Result<RomanFraction, RomanNumeralError> {
+        let underflow = RomanNumeralError::NonPositiveNumeral;
+        self.checked_op(rhs.as_twenty_fourths(), u64::checked_sub, underflow)
+    }
+
+    /// Multiplies by a whole number, like a price by a quantity, failing with
+    /// [`RomanNumeralError::Overflow`] if the product is too large.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use roman_numerals::{RomanFraction, RomanNumeral};
+    ///
+    /// let price = RomanFraction::from_str("S·")?;
+    /// assert_eq!(price.checked_mul(RomanNumeral::from_str("III")?)?.to_string(), "IS···");
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub fn checked_mul(self, rhs: RomanNumeral) -> Result<RomanFraction, RomanNumeralError> {
+        let overflow = RomanNumeralError::Overflow;
+        self.checked_op(u64::from(rhs.to_int()), u64::checked_mul, overflow)
+    }
+
+    /// Reads a whole part in canonical subtractive notation, which may be left out for
+    /// fractions less than one, followed by at most one S, at most five unciae and at most
+    /// one Ɛ, in that order. Case is ignored, ∴ counts as three unciae, and "." and "E"
+    /// stand in for · and Ɛ. [`FromStr`] does the same.
+    ///
+    /// ```
+    /// use roman_numerals::{RomanFraction, RomanNumeralError};
+    ///
+    /// assert_eq!(RomanFraction::parse("xiis..")?.to_string(), "XIIS··");
+    /// assert_eq!(RomanFraction::parse("S∴·")?.twelfths(), 10);
+    /// assert!(matches!(
+    ///     RomanFraction::parse("······"),
+    ///     Err(RomanNumeralError::TooManyUnciae('·', _))
+    /// ));
+    /// # Ok::<(), RomanNumeralError>(())
+    /// ```
+    pub fn parse(s: &str) -> Result<RomanFraction, RomanNumeralError> {
+        let split = s.find(|c| sign_value(c).is_some()).unwrap_or(s.len());
+        let (whole, signs) = s.split_at(split);
+        let whole = match whole {
+            "" if signs.is_empty() => return Err(RomanNumeralError::EmptyInput),
+            "" => 0,
+            whole => u64::from(RomanNumeral::from_str(whole)?.to_int()),
+        };
+
+        let start = Position {
+            byte: split,
+            char: s[..split].chars().count(),
+        };
+        let mut semis = false;
+        let mut unciae = 0;
+        let mut semuncia = false;
+        for (i, (byte, c)) in signs.char_indices().enumerate() {
+            let pos = Position {
+                byte: start.byte + byte,
+                char: start.char + i,
+            };
+            match sign_value(c) {
+                // a numeral after the fraction signs
+                None if char_to_value(c, pos).is_ok() => {
+                    return Err(RomanNumeralError::OutOfOrder(c, pos))
+                }
+                None => return Err(RomanNumeralError::InvalidChar(c, pos)),
+                Some(12) if semis => return Err(RomanNumeralError::RepeatedSymbol(c, pos)),
+                Some(12) if unciae > 0 || semuncia => {
+                    return Err(RomanNumeralError::OutOfOrder(c, pos))
+                }
+                Some(12) => semis = true,
+                Some(1) if semuncia => return Err(RomanNumeralError::RepeatedSymbol(c, pos)),
+                Some(1) => semuncia = true,
+                Some(_) if semuncia => return Err(RomanNumeralError::OutOfOrder(c, pos)),
+                Some(n) => {
+                    unciae += n / 2;
+                    if unciae > 5 {
+                        return Err(RomanNumeralError::TooManyUnciae(c, pos));
+                    }
+                }
+            }
+        }
+
+        let twenty_fourths = if semis { 12 } else { 0 } + unciae * 2 + u64::from(semuncia);
+        RomanFraction::from_twenty_fourths(whole * 24 + twenty_fourths)
+    }
+
+    /// Something to put in `format!` and friends that writes the fraction with "." and "E"
+    /// in place of · and Ɛ, honoring fill, width, alignment and precision like
+    /// [`Display`]. `{:#}` writes it in lowercase.
+    ///
+    /// ```
+    /// use roman_numerals::RomanFraction;
+    ///
+    /// let n = RomanFraction::from_twenty_fourths(63)?;
+    /// assert_eq!(n.to_string(), "IIS·Ɛ");
+    /// assert_eq!(n.display_ascii().to_string(), "IIS.E");
+    /// assert_eq!(format!("{:#}", n.display_ascii()), "iis.e");
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub fn display_ascii(&self) -> DisplayAscii<'_> {
+        DisplayAscii { fraction: self }
+    }
+
+    /// Writes the fraction like [`RomanFraction::display_ascii`].
+    #[cfg(feature = "alloc")]
+    pub fn to_ascii_string(&self) -> String {
+        let mut result = String::new();
+        self.write_fraction(&mut result, false, true)
+            .expect("writing to a String never fails");
+        result
+    }
+
+    // writes the whole part and then the signs for what's left, the largest first
+    fn write_fraction(&self, mut w: &mut dyn Write, lowercase: bool, ascii: bool) -> fmt::Result {
+        if let Some(whole) = self.whole_numeral() {
+            whole.write_roman_with(&mut w, FormatOptions::new().lowercase(lowercase))?;
+        }
+        let twelfths = self.twelfths();
+        if twelfths >= 6 {
+            w.write_char(if lowercase { 's' } else { 'S' })?;
+        }
+        let uncia = if ascii { '.' } else { '·' };
+        for _ in 0..twelfths % 6 {
+            w.write_char(uncia)?;
+        }
+        if self.has_semuncia() {
+            w.write_char(match (ascii, lowercase) {
+                (true, true) => 'e',
+                (true, false) => 'E',
+                (false, true) => 'ɛ',
+                (false, false) => 'Ɛ',
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays a [`RomanFraction`] in ASCII, see [`RomanFraction::display_ascii`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayAscii<'a> {
+    fraction: &'a RomanFraction,
+}
+
+impl Display for DisplayAscii<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lowercase = f.alternate();
+        pad(f, |w| self.fraction.write_fraction(w, lowercase, true))
+    }
+}
+
+// fill, width, alignment and precision apply to the fraction as a whole, `{:#}` writes it in
+// lowercase
+impl Display for RomanFraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lowercase = f.alternate();
+        pad(f, |w| self.write_fraction(w, lowercase, false))
+    }
+}
+
+impl FromStr for RomanFraction {
+    type Err = RomanNumeralError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RomanFraction::parse(s)
+    }
+}
+
+impl From<RomanNumeral> for RomanFraction {
+    fn from(n: RomanNumeral) -> RomanFraction {
+        RomanFraction::new(n.to_int(), 0).expect("every numeral is a whole fraction")
+    }
+}
+
+// the operators panic where the checked methods fail, like the ones for numerals
+impl Add for RomanFraction {
+    type Output = RomanFraction;
+    fn add(self, rhs: RomanFraction) -> RomanFraction {
+        self.checked_add(rhs)
+            .unwrap_or_else(|e| panic!("cannot add roman fractions: {e}"))
+    }
+}
+
+impl AddAssign for RomanFraction {
+    fn add_assign(&mut self, rhs: RomanFraction) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for RomanFraction {
+    type Output = RomanFraction;
+    fn sub(self, rhs: RomanFraction) -> RomanFraction {
+        self.checked_sub(rhs)
+            .unwrap_or_else(|e| panic!("cannot subtract roman fractions: {e}"))
+    }
+}
+
+impl SubAssign for RomanFraction {
+    fn sub_assign(&mut self, rhs: RomanFraction) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<RomanNumeral> for RomanFraction {
+    type Output = RomanFraction;
+    fn mul(self, rhs: RomanNumeral) -> RomanFraction {
+        self.checked_mul(rhs)
+            .unwrap_or_else(|e| panic!("cannot multiply roman fractions: {e}"))
+    }
+}
+
+impl MulAssign<RomanNumeral> for RomanFraction {
+    fn mul_assign(&mut self, rhs: RomanNumeral) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{format, str::FromStr, string::ToString};
+
+    use crate::{Position, RomanFraction, RomanNumeral, RomanNumeralError};
+
+    fn fraction(s: &str) -> RomanFraction {
+        RomanFraction::from_str(s).unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn round_trips() {
+        for n in 1..24 * 50 {
+            let f = RomanFraction::from_twenty_fourths(n).unwrap();
+            assert_eq!(fraction(&f.to_string()), f);
+            assert_eq!(fraction(&f.to_ascii_string()), f);
+            assert_eq!(fraction(&format!("{f:#}")), f);
+        }
+    }
+
+    #[test]
+    fn parses() {
+        assert_eq!(fraction("XIIS··").as_twenty_fourths(), 12 * 24 + 16);
+        assert_eq!(fraction("S").to_rational(), (1, 2));
+        assert_eq!(fraction("∴").twelfths(), 3);
+        assert_eq!(fraction("··∴"), fraction("·····"));
+        assert_eq!(fraction("IƐ").to_rational(), (25, 24));
+        assert_eq!(
+            fraction("MCMLXXXIV"),
+            RomanNumeral::with_value(1984).unwrap().into()
+        );
+        assert_eq!(fraction("ⅫS").whole(), 12);
+    }
+
+    #[test]
+    fn rejects() {
+        let pos = |byte, char| Position { byte, char };
+        let err = |s| RomanFraction::from_str(s).unwrap_err();
+        assert_eq!(err(""), RomanNumeralError::EmptyInput);
+        assert_eq!(
+            err("IIII·"),
+            RomanNumeralError::TooManyRepeats('I', pos(3, 3))
+        );
+        assert_eq!(
+            err("XSS"),
+            RomanNumeralError::RepeatedSymbol('S', pos(2, 2))
+        );
+        assert_eq!(err("X·S"), RomanNumeralError::OutOfOrder('S', pos(3, 2)));
+        assert_eq!(err("SX"), RomanNumeralError::OutOfOrder('X', pos(1, 1)));
+        assert_eq!(err("Ɛ·"), RomanNumeralError::OutOfOrder('·', pos(2, 1)));
+        assert_eq!(
+            err("·∴··"),
+            RomanNumeralError::TooManyUnciae('·', pos(7, 3))
+        );
+        assert_eq!(err("S ·"), RomanNumeralError::InvalidChar(' ', pos(1, 1)));
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(fraction("S") + fraction("S"), fraction("I"));
+        assert_eq!(fraction("II") - fraction("·"), fraction("IS·····"));
+        assert_eq!(
+            fraction("·Ɛ") * RomanNumeral::with_value(8).unwrap(),
+            fraction("I")
+        );
+        assert_eq!(
+            fraction("S").checked_sub(fraction("S")),
+            Err(RomanNumeralError::NonPositiveNumeral)
+        );
+        let max = RomanFraction::new(u32::MAX, 11).unwrap();
+        assert_eq!(
+            max.checked_add(fraction("·")),
+            Err(RomanNumeralError::Overflow)
+        );
+        assert!(fraction("S") < fraction("S·"));
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(RomanFraction::new(0, 11).unwrap().to_string(), "S·····");
+        assert_eq!(RomanFraction::new(4, 0).unwrap().to_string(), "IV");
+        assert_eq!(format!("[{:>6}]", fraction("XS·")), "[   XS·]");
+        assert_eq!(format!("{:#}", fraction("XS·Ɛ")), "xs·ɛ");
+        assert_eq!(
+            RomanFraction::new(0, 0),
+            Err(RomanNumeralError::NonPositiveNumeral)
+        );
+    }
+}
@@ -0,0 +1,536 @@
+//! Conversion between integers and roman numerals.
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use std::str::FromStr;
+//! use roman_numerals::RomanNumeral;
+//!
+//! let year = RomanNumeral::from_str("MCMLXXXIV").unwrap();
+//! assert_eq!(year.to_int(), 1984);
+//! assert_eq!(year.to_roman_numeral_string(), "MCMLXXXIV");
+//! # }
+//! ```
+//!
+//! Without the default `std` feature the crate is `no_std`, and parsing and formatting
+//! through [`core::fmt`] never allocate. The `alloc` feature brings back the methods that
+//! return a [`String`] and the apostrophus and vinculum parsers.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// the tests use the standard library whichever features are enabled
+#[cfg(test)]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::{fmt::Display, num::NonZeroU32, str::FromStr};
+
+mod apostrophus;
+mod arithmetic;
+mod convert;
+mod error;
+mod explain;
+mod format;
+mod fraction;
+mod macros;
+mod parse;
+mod range;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod text;
+mod unicode;
+mod vinculum;
+
+pub use apostrophus::ApostrophusStyle;
+pub use error::{Position, RomanNumeralError};
+pub use explain::{FormatStep, FormatSteps, Operation, ParseStep, ParseSteps};
+pub use format::{DisplayWith, FormatOptions, NumeralStyle, RomanStr, MAX_CANONICAL_LEN};
+pub use fraction::{DisplayAscii, RomanFraction};
+pub use range::NumeralRange;
+#[cfg(feature = "alloc")]
+pub use text::convert_text;
+pub use text::{TextDirection, TextOptions};
+pub use unicode::DisplayUnicode;
+pub use vinculum::VinculumStyle;
+
+use parse::{parse_lenient, parse_plain_strict, plain_symbols};
+
+/// How forgiving [`RomanNumeral::parse`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Only accept numerals exactly as [`RomanNumeral::to_roman_numeral_string`] would
+    /// emit them.
+    #[default]
+    Strict,
+    /// Sum the symbols, subtracting any symbol that is followed by a larger one, so "IIII"
+    /// is 4 and "IC" is 99.
+    Lenient,
+}
+
+/// A number that can be converted to and from roman numerals. Zero and negative numbers
+/// have no numeral, so the value is always at least 1.
+///
+/// Numerals compare and sort by their value, so MCM comes after CM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RomanNumeral {
+    value: NonZeroU32,
+}
+
+pub(crate) const fn greatest_str_leq_than_n(v: i64) -> (&'static str, i64) {
+    match v {
+        1000.. => ("M", 1000),
+        900.. => ("CM", 900),
+        500.. => ("D", 500),
+        400.. => ("CD", 400),
+        100.. => ("C", 100),
+        90.. => ("XC", 90),
+        50.. => ("L", 50),
+        40.. => ("XL", 40),
+        10.. => ("X", 10),
+        9.. => ("IX", 9),
+        5.. => ("V", 5),
+        4.. => ("IV", 4),
+        1.. => ("I", 1),
+        _ => ("", 0),
+    }
+}
+
+// appends the canonical numeral for `val`, nothing at all for 0
+#[cfg(feature = "alloc")]
+pub(crate) fn push_numeral(result: &mut String, mut val: i64) {
+    while val > 0 {
+        let (s, v) = greatest_str_leq_than_n(val);
+        val -= v;
+        result.push_str(s);
+    }
+}
+
+impl RomanNumeral {
+    /// The smallest numeral, I.
+    pub const MIN: RomanNumeral = RomanNumeral {
+        value: NonZeroU32::MIN,
+    };
+
+    /// The largest numeral. Every notation can write it, but only by repeating its largest
+    /// symbol many times over.
+    pub const MAX: RomanNumeral = RomanNumeral {
+        value: NonZeroU32::MAX,
+    };
+
+    /// The largest numeral that can be written in standard notation without repeating M
+    /// more than three times, MMMCMXCIX.
+    pub const MAX_STANDARD: RomanNumeral = RomanNumeral {
+        value: NonZeroU32::new(3_999).unwrap(),
+    };
+
+    /// The largest numeral that can be written with
+    /// [`RomanNumeral::to_apostrophus_string`] without repeating CCCIↃↃↃ more than three
+    /// times.
+    pub const MAX_APOSTROPHUS: RomanNumeral = RomanNumeral {
+        value: NonZeroU32::new(399_999).unwrap(),
+    };
+
+    /// The largest numeral that can be written with [`RomanNumeral::to_vinculum_string`]
+    /// without repeating the doubly marked M more than three times.
+    pub const MAX_VINCULUM: RomanNumeral = RomanNumeral {
+        value: NonZeroU32::new(399_999_999).unwrap(),
+    };
+
+    /// A numeral with the value `value`.
+    pub const fn new(value: NonZeroU32) -> RomanNumeral {
+        RomanNumeral { value }
+    }
+
+    /// A numeral with the value `v`, failing with [`RomanNumeralError::NonPositiveNumeral`]
+    /// for 0. See also the [`TryFrom`] impls for other integer types.
+    ///
+    /// ```
+    /// use roman_numerals::{RomanNumeral, RomanNumeralError};
+    ///
+    /// assert_eq!(RomanNumeral::with_value(7).unwrap().to_int(), 7);
+    /// assert_eq!(RomanNumeral::with_value(0).unwrap_err(), RomanNumeralError::NonPositiveNumeral);
+    /// ```
+    pub const fn with_value(v: u32) -> Result<RomanNumeral, RomanNumeralError> {
+        match NonZeroU32::new(v) {
+            Some(value) => Ok(RomanNumeral { value }),
+            None => Err(RomanNumeralError::NonPositiveNumeral),
+        }
+    }
+
+    // the numerals are worked out in i64 so intermediate results like the sum of a lenient
+    // parse have room to go out of range before they're checked here
+    pub(crate) const fn from_i64(v: i64) -> Result<RomanNumeral, RomanNumeralError> {
+        if v < 1 {
+            Err(RomanNumeralError::NonPositiveNumeral)
+        } else if v > u32::MAX as i64 {
+            Err(RomanNumeralError::Overflow)
+        } else {
+            RomanNumeral::with_value(v as u32)
+        }
+    }
+
+    /// The value of the numeral.
+    pub const fn to_int(&self) -> u32 {
+        self.value.get()
+    }
+
+    /// The value of the numeral as a [`NonZeroU32`].
+    pub const fn get(&self) -> NonZeroU32 {
+        self.value
+    }
+
+    // the value in the type the conversions work in
+    pub(crate) const fn value(&self) -> i64 {
+        self.value.get() as i64
+    }
+
+    /// Writes the numeral in canonical subtractive notation, like "MCMLXXXIV" for 1984.
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let year = RomanNumeral::with_value(1984).unwrap();
+    /// assert_eq!(year.to_roman_numeral_string(), "MCMLXXXIV");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_roman_numeral_string(&self) -> String {
+        // every thousand is an M, and the longest numeral below a thousand is DCCCLXXXVIII
+        let mut result = String::with_capacity(self.value() as usize / 1000 + 12);
+        push_numeral(&mut result, self.value());
+        result
+    }
+
+    /// Reads a numeral, ignoring case. Characters from the Number Forms block like Ⅻ are
+    /// read as the letters they stand for. [`FromStr`] does the same in [`ParseMode::Strict`].
+    ///
+    /// ```
+    /// use roman_numerals::{ParseMode, RomanNumeral};
+    ///
+    /// assert!(RomanNumeral::parse("IIII", ParseMode::Strict).is_err());
+    /// assert_eq!(RomanNumeral::parse("IIII", ParseMode::Lenient).unwrap().to_int(), 4);
+    /// ```
+    pub fn parse(s: &str, mode: ParseMode) -> Result<RomanNumeral, RomanNumeralError> {
+        let value = match mode {
+            ParseMode::Strict => parse_plain_strict(s)?,
+            ParseMode::Lenient => parse_lenient(plain_symbols(s))?,
+        };
+        RomanNumeral::from_i64(value)
+    }
+
+    /// The same as [`FromStr`], but usable in constants. See also [`roman!`].
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// const YEAR: RomanNumeral = match RomanNumeral::from_str_const("MMXXIII") {
+    ///     Ok(year) => year,
+    ///     Err(_) => panic!("not a numeral"),
+    /// };
+    /// assert_eq!(YEAR.to_int(), 2023);
+    /// ```
+    pub const fn from_str_const(s: &str) -> Result<RomanNumeral, RomanNumeralError> {
+        match parse_plain_strict(s) {
+            Ok(value) => RomanNumeral::from_i64(value),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes the numeral like [`RomanNumeral::to_roman_numeral_string`] into a fixed-size
+    /// buffer, usable in constants. Anything above 3999 may not fit and fail with
+    /// [`RomanNumeralError::BufferTooSmall`].
+    ///
+    /// ```
+    /// use roman_numerals::{RomanNumeral, RomanStr};
+    ///
+    /// const CHAPTER: RomanStr = match RomanNumeral::from_str_const("XIV") {
+    ///     Ok(n) => match n.to_roman_str() {
+    ///         Ok(s) => s,
+    ///         Err(_) => panic!("too long"),
+    ///     },
+    ///     Err(_) => panic!("not a numeral"),
+    /// };
+    /// assert_eq!(CHAPTER.as_str(), "XIV");
+    /// ```
+    pub const fn to_roman_str(&self) -> Result<RomanStr, RomanNumeralError> {
+        let mut result = RomanStr::new();
+        let mut val = self.value();
+
+        while val > 0 {
+            let (s, v) = greatest_str_leq_than_n(val);
+            val -= v;
+            if !result.push_str(s) {
+                return Err(RomanNumeralError::BufferTooSmall);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl FromStr for RomanNumeral {
+    type Err = RomanNumeralError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RomanNumeral::from_str_const(s)
+    }
+}
+
+// fill, width, alignment and precision apply to the numeral as a whole, `{:#}` writes it in
+// lowercase
+impl Display for RomanNumeral {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.display_with(FormatOptions::new()).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::panic;
+    use std::{format, str::FromStr, string::ToString, vec::Vec};
+    #[cfg(feature = "alloc")]
+    use std::{string::String, vec};
+
+    use crate::{roman, ParseMode, Position, RomanNumeral, RomanNumeralError};
+    #[cfg(feature = "alloc")]
+    use crate::{ApostrophusStyle, VinculumStyle};
+
+    #[test]
+    fn decreasing_digit_numerals() {
+        assert_eq!(RomanNumeral::from_str("MMXXIII").unwrap().to_int(), 2023);
+        assert_eq!(RomanNumeral::from_str("MDCLXVI").unwrap().to_int(), 1666);
+    }
+
+    #[test]
+    fn increasing_digit_numerals() {
+        assert_eq!(RomanNumeral::from_str("IV").unwrap().to_int(), 4);
+        assert_eq!(RomanNumeral::from_str("IX").unwrap().to_int(), 9);
+    }
+
+    #[test]
+    fn general_numerals() {
+        assert_eq!(RomanNumeral::from_str("MCMLXXXIV").unwrap().to_int(), 1984);
+        assert_eq!(RomanNumeral::from_str("MCCXCIII").unwrap().to_int(), 1293);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_string() {
+        let _ = RomanNumeral::from_str("").unwrap();
+    }
+
+    #[test]
+    fn invalid_characters() {
+        for chr in "abefghjknopqrstuwyz".chars() {
+            let r = RomanNumeral::from_str(&format!("{chr}"));
+            if r.is_ok() {
+                panic!("expecting only invalid characters, got valid character {chr:?}");
+            }
+        }
+        for chr in ":.,;?! \t\n\r".chars() {
+            let r = RomanNumeral::from_str(&format!("{chr}"));
+            if r.is_ok() {
+                panic!("expecting only invalid characters, got valid character {chr:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn valid_characters() {
+        for chr in "cdilmvx".chars() {
+            let r = RomanNumeral::from_str(&format!("{chr}"));
+            if let Err(err) = r {
+                panic!("{err:?}");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn convert_back_and_forth() {
+        for i in 1..10_000 {
+            let string = RomanNumeral::with_value(i)
+                .unwrap()
+                .to_roman_numeral_string();
+            assert_eq!(
+                i,
+                RomanNumeral::from_str(&string).unwrap().to_int(),
+                "result of to_string(): {:?}",
+                string,
+            );
+        }
+    }
+
+    #[test]
+    fn strict_rejects_non_canonical() {
+        for s in [
+            "IIIIIIII", "IC", "VX", "MCMC", "IIV", "VV", "IXI", "XCX", "DCD",
+        ] {
+            assert!(
+                RomanNumeral::from_str(s).is_err(),
+                "expected {s:?} to be rejected"
+            );
+        }
+        assert!(matches!(
+            RomanNumeral::from_str("IC"),
+            Err(RomanNumeralError::IllegalSubtractivePair('I', 'C', _))
+        ));
+        assert!(matches!(
+            RomanNumeral::from_str("VX"),
+            Err(RomanNumeralError::IllegalSubtractivePair('V', 'X', _))
+        ));
+        assert!(matches!(
+            RomanNumeral::from_str("LL"),
+            Err(RomanNumeralError::RepeatedSymbol('L', _))
+        ));
+        assert!(matches!(
+            RomanNumeral::from_str("XXXX"),
+            Err(RomanNumeralError::TooManyRepeats('X', _))
+        ));
+        assert!(matches!(
+            RomanNumeral::from_str("MCMC"),
+            Err(RomanNumeralError::OutOfOrder('C', _))
+        ));
+        assert!(matches!(
+            RomanNumeral::from_str("IIV"),
+            Err(RomanNumeralError::OutOfOrder('I', _))
+        ));
+    }
+
+    #[test]
+    fn lenient_sums_anything() {
+        let lenient = |s| RomanNumeral::parse(s, ParseMode::Lenient).unwrap().to_int();
+        assert_eq!(lenient("IIIIIIII"), 8);
+        assert_eq!(lenient("IC"), 99);
+        assert_eq!(lenient("VX"), 5);
+        assert_eq!(lenient("MCMC"), 2000);
+        assert_eq!(lenient("IIV"), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn strict_accepts_exactly_canonical() {
+        let mut strings = vec![String::new()];
+        for _ in 0..5 {
+            strings = strings
+                .iter()
+                .flat_map(|s| "IVXLCDM".chars().map(move |c| format!("{s}{c}")))
+                .collect();
+            for s in &strings {
+                let canonical = RomanNumeral::parse(s, ParseMode::Lenient)
+                    .is_ok_and(|n| &n.to_roman_numeral_string() == s);
+                assert_eq!(canonical, RomanNumeral::from_str(s).is_ok(), "{s:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn error_positions() {
+        let err = RomanNumeral::from_str("MCMC").unwrap_err();
+        assert_eq!(err.position(), Some(Position { byte: 3, char: 3 }));
+
+        // multi-byte characters make byte offsets and char indices diverge
+        let err = RomanNumeral::from_str("ⅯXé").unwrap_err();
+        assert_eq!(
+            err,
+            RomanNumeralError::InvalidChar('é', Position { byte: 4, char: 2 })
+        );
+        let err = RomanNumeral::from_str("XXé").unwrap_err();
+        assert_eq!(
+            err,
+            RomanNumeralError::InvalidChar('é', Position { byte: 2, char: 2 })
+        );
+        let err = RomanNumeral::parse("ΧXé", ParseMode::Lenient).unwrap_err();
+        assert_eq!(err.position(), Some(Position { byte: 0, char: 0 }));
+
+        assert_eq!(
+            RomanNumeral::from_str("").unwrap_err(),
+            RomanNumeralError::EmptyInput
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn render_points_at_error() {
+        let err = RomanNumeral::from_str("MMXXXXI").unwrap_err();
+        assert_eq!(
+            err.render("MMXXXXI"),
+            "MMXXXXI\n     ^ 'X' repeated more than three times at position 5"
+        );
+
+        // the overlines sit on the symbols before them, so the caret counts past them
+        let input = "X\u{305}V\u{305}Q";
+        let err = RomanNumeral::from_vinculum_str(input).unwrap_err();
+        assert_eq!(
+            err.render(input),
+            format!("{input}\n  ^ invalid character 'Q' at position 4")
+        );
+    }
+
+    #[test]
+    fn display() {
+        let n = RomanNumeral::with_value(1984).unwrap();
+        assert_eq!(format!("{n}"), "MCMLXXXIV");
+        assert_eq!(format!("{n:#}"), "mcmlxxxiv");
+        assert_eq!(format!("{n:>12}"), "   MCMLXXXIV");
+        assert_eq!(format!("{n:*<12}"), "MCMLXXXIV***");
+        assert_eq!(format!("{n:^#13}"), "  mcmlxxxiv  ");
+        assert_eq!(format!("{n:.4}"), "MCML");
+    }
+
+    #[test]
+    fn sorts_by_value() {
+        let mut numerals: Vec<_> = ["MCM", "CM", "IX", "XI", "CM", "V"]
+            .into_iter()
+            .map(|s| RomanNumeral::from_str(s).unwrap())
+            .collect();
+        numerals.sort();
+        numerals.dedup();
+        let sorted: Vec<_> = numerals.iter().map(|n| n.to_int()).collect();
+        assert_eq!(sorted, [5, 9, 11, 900, 1900]);
+
+        let set: std::collections::HashSet<_> = ["X", "x", "Ⅹ"]
+            .into_iter()
+            .map(|s| RomanNumeral::from_str(s).unwrap())
+            .collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn limits() {
+        assert_eq!(RomanNumeral::MIN.to_roman_numeral_string(), "I");
+        assert_eq!(RomanNumeral::MAX.to_int(), u32::MAX);
+        assert_eq!(
+            RomanNumeral::MAX_STANDARD.to_roman_numeral_string(),
+            "MMMCMXCIX"
+        );
+        assert_eq!(
+            RomanNumeral::MAX_APOSTROPHUS.to_apostrophus_string(ApostrophusStyle::Unicode),
+            "CCCIↃↃↃCCCIↃↃↃCCCIↃↃↃCCIↃↃCCCIↃↃↃCIↃCCIↃↃCCIↃXCIX"
+        );
+        assert_eq!(
+            RomanNumeral::MAX_VINCULUM.to_vinculum_string(VinculumStyle::Parentheses),
+            "((MMMCMXCIX))(XCIX)CMXCIX"
+        );
+        assert!(RomanNumeral::MIN < RomanNumeral::MAX_STANDARD);
+        assert!(RomanNumeral::MAX_VINCULUM < RomanNumeral::MAX);
+    }
+
+    #[test]
+    fn const_conversions() {
+        const FOURTEEN: u32 = roman!(XIV);
+        assert_eq!(FOURTEEN, 14);
+        assert_eq!(roman!("mcmlxxxiv"), 1984);
+        assert_eq!(roman!("Ⅻ"), 12);
+
+        for i in 1..4_000 {
+            let n = RomanNumeral::with_value(i).unwrap();
+            assert_eq!(n.to_roman_str().unwrap().as_str(), n.to_string());
+        }
+        assert_eq!(
+            RomanNumeral::with_value(8_888).unwrap().to_roman_str(),
+            Err(RomanNumeralError::BufferTooSmall)
+        );
+    }
+}
@@ -0,0 +1,34 @@
+/// Reads a roman numeral at compile time and expands to its value as a `u32` constant.
+///
+/// The numeral can be written bare or as a string literal and follows the same rules as
+/// [`FromStr`](core::str::FromStr) for [`RomanNumeral`](crate::RomanNumeral), so a malformed
+/// numeral fails compilation with the rule it broke.
+///
+/// ```
+/// use roman_numerals::roman;
+///
+/// const ORWELL: u32 = roman!(MCMLXXXIV);
+/// assert_eq!(ORWELL, 1984);
+/// assert_eq!(roman!("mmxxiii"), 2023);
+/// ```
+///
+/// ```compile_fail
+/// // symbols have to go from largest to smallest
+/// let _ = roman_numerals::roman!(MCMC);
+/// ```
+#[macro_export]
+macro_rules! roman {
+    (@str $numeral:expr) => {{
+        const VALUE: u32 = match $crate::RomanNumeral::from_str_const($numeral) {
+            Ok(numeral) => numeral.to_int(),
+            Err(e) => panic!("{}", e.rule()),
+        };
+        VALUE
+    }};
+    ($numeral:ident) => {
+        $crate::roman!(@str stringify!($numeral))
+    };
+    ($numeral:literal) => {
+        $crate::roman!(@str $numeral)
+    };
+}
@@ -0,0 +1,388 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{
+    unicode::{is_ligature, number_form_values},
+    Position, RomanNumeralError,
+};
+
+// a symbol read from the input along with the position and char it starts at, a single
+// symbol can take up several chars like CIↃ does
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Symbol {
+    pub(crate) pos: Position,
+    pub(crate) c: char,
+    pub(crate) value: i64,
+}
+
+// the rules [`parse_strict`] enforces differ slightly between notations
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Notation {
+    Standard,
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    Apostrophus,
+}
+
+impl Notation {
+    // how many times the unit `value` may be repeated within its decimal place
+    const fn max_repeats(self, value: i64, seen_five: bool) -> u32 {
+        match self {
+            Notation::Standard if value == 1000 => u32::MAX,
+            Notation::Apostrophus if value == 100_000 => u32::MAX,
+            // C IↃ would read as CIↃ, so 400 is written CCCC
+            Notation::Apostrophus if value == 100 && !seen_five => 4,
+            _ => 3,
+        }
+    }
+
+    // whether a smaller symbol may be subtracted from `value`. Plain numerals stop at CM
+    // and don't write the thousands past it as Mↂ, and as C IↃ would read as CIↃ, 400 is
+    // only ever CCCC in apostrophus numerals
+    const fn can_subtract_from(self, value: i64) -> bool {
+        match self {
+            Notation::Standard => value <= 1000,
+            Notation::Apostrophus => value != 500,
+        }
+    }
+}
+
+pub(crate) const fn char_to_value(c: char, pos: Position) -> Result<i64, RomanNumeralError> {
+    match c.to_ascii_lowercase() {
+        'm' => Ok(1000),
+        'd' => Ok(500),
+        'c' => Ok(100),
+        'l' => Ok(50),
+        'x' => Ok(10),
+        'v' => Ok(5),
+        'i' => Ok(1),
+        _ => Err(RomanNumeralError::InvalidChar(c, pos)),
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn positioned_chars(s: &str) -> impl Iterator<Item = (Position, char)> + '_ {
+    s.char_indices()
+        .enumerate()
+        .map(|(char, (byte, c))| (Position { byte, char }, c))
+}
+
+// appends the symbols `c` stands for, which is several for ligatures like Ⅻ
+#[cfg(feature = "alloc")]
+pub(crate) fn push_symbols(
+    symbols: &mut Vec<Symbol>,
+    pos: Position,
+    c: char,
+) -> Result<(), RomanNumeralError> {
+    match number_form_values(c) {
+        Some(values) => symbols.extend(values.iter().map(|&value| Symbol { pos, c, value })),
+        None => symbols.push(Symbol {
+            pos,
+            c,
+            value: char_to_value(c, pos)?,
+        }),
+    }
+    Ok(())
+}
+
+pub(crate) fn plain_symbols(s: &str) -> PlainSymbols<'_> {
+    PlainSymbols::new(s)
+}
+
+// the char starting at `byte` along with its length, `bytes` has to be valid UTF-8
+const fn decode_utf8(bytes: &[u8], byte: usize) -> (char, usize) {
+    let first = bytes[byte] as u32;
+    let (len, mut code) = match first {
+        0x00..=0x7f => (1, first),
+        0xc0..=0xdf => (2, first & 0x1f),
+        0xe0..=0xef => (3, first & 0x0f),
+        _ => (4, first & 0x07),
+    };
+    let mut i = 1;
+    while i < len {
+        code = (code << 6) | (bytes[byte + i] as u32 & 0x3f);
+        i += 1;
+    }
+    match char::from_u32(code) {
+        Some(c) => (c, len),
+        None => (char::REPLACEMENT_CHARACTER, len),
+    }
+}
+
+// reads the symbols of a plain numeral one at a time, without allocating so it can run at
+// compile time
+#[derive(Clone, Copy)]
+pub(crate) struct PlainSymbols<'a> {
+    bytes: &'a [u8],
+    next: Position,
+    // the rest of a ligature like Ⅻ that has been partially read
+    pending: &'static [i64],
+    pending_pos: Position,
+    pending_c: char,
+}
+
+impl<'a> PlainSymbols<'a> {
+    const fn new(s: &'a str) -> PlainSymbols<'a> {
+        let start = Position { byte: 0, char: 0 };
+        PlainSymbols {
+            bytes: s.as_bytes(),
+            next: start,
+            pending: &[],
+            pending_pos: start,
+            pending_c: ' ',
+        }
+    }
+
+    const fn next_symbol(&mut self) -> Option<Result<Symbol, RomanNumeralError>> {
+        if let [value, rest @ ..] = self.pending {
+            self.pending = rest;
+            let (pos, c) = (self.pending_pos, self.pending_c);
+            return Some(Ok(Symbol {
+                pos,
+                c,
+                value: *value,
+            }));
+        }
+        if self.next.byte >= self.bytes.len() {
+            return None;
+        }
+
+        let pos = self.next;
+        let (c, len) = decode_utf8(self.bytes, pos.byte);
+        self.next = Position {
+            byte: pos.byte + len,
+            char: pos.char + 1,
+        };
+        match number_form_values(c) {
+            Some([value, rest @ ..]) => {
+                self.pending = rest;
+                self.pending_pos = pos;
+                self.pending_c = c;
+                Some(Ok(Symbol {
+                    pos,
+                    c,
+                    value: *value,
+                }))
+            }
+            _ => match char_to_value(c, pos) {
+                Ok(value) => Some(Ok(Symbol { pos, c, value })),
+                Err(e) => Some(Err(e)),
+            },
+        }
+    }
+}
+
+impl Iterator for PlainSymbols<'_> {
+    type Item = Result<Symbol, RomanNumeralError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_symbol()
+    }
+}
+
+// the pairwise sum from before strict parsing existed, only the last symbol is always added
+pub(crate) fn parse_lenient(
+    symbols: impl Iterator<Item = Result<Symbol, RomanNumeralError>>,
+) -> Result<i64, RomanNumeralError> {
+    let mut res = 0;
+    let mut previous: Option<Symbol> = None;
+
+    for next in symbols {
+        let next = next?;
+        if let Some(current) = previous {
+            res += if current.value < next.value {
+                // if the next value is greater, we subtract the current value
+                -current.value
+            } else {
+                current.value
+            };
+        }
+        previous = Some(next);
+    }
+
+    // last character always has its value added
+    let last = previous.ok_or(RomanNumeralError::EmptyInput)?;
+    Ok(res + last.value)
+}
+
+// I, X, C, M and so on may be repeated and used subtractively, V, L, D and so on may not
+const fn is_power_of_ten(v: i64) -> bool {
+    10_i64.pow(v.ilog10()) == v
+}
+
+// the decimal digit a symbol contributes to, ones being 0
+const fn decimal_place(v: i64) -> u32 {
+    v.ilog10()
+}
+
+enum Token {
+    // a single I, X, C or M
+    Unit,
+    // a single V, L or D
+    Five,
+    // a smaller symbol followed by a larger one, like IV or CM
+    Subtractive,
+}
+
+// reads the numeral one decimal place at a time, each place has to be either a single
+// subtractive pair or an optional five followed by at most three units (the notation's
+// largest unit excepted)
+struct StrictParser {
+    notation: Notation,
+    value: i64,
+    // what has been seen so far in the current decimal place
+    place: u32,
+    seen_five: bool,
+    seen_subtractive: bool,
+    units: u32,
+    // the char of a ligature like Ⅻ read in the current decimal place
+    ligature: Option<usize>,
+}
+
+impl StrictParser {
+    const fn new(notation: Notation) -> StrictParser {
+        StrictParser {
+            notation,
+            value: 0,
+            place: u32::MAX,
+            seen_five: false,
+            seen_subtractive: false,
+            units: 0,
+            ligature: None,
+        }
+    }
+
+    // reads `current`, along with `next` if the two make up a subtractive pair, returning
+    // whether `next` was used
+    const fn step(
+        &mut self,
+        current: Symbol,
+        next: Option<Symbol>,
+    ) -> Result<bool, RomanNumeralError> {
+        let Symbol {
+            pos,
+            c,
+            value: current,
+        } = current;
+        let (value, token) = match next {
+            Some(next) if next.value > current => {
+                // the last symbol of a ligature doesn't pair with the next char, like ⅪV
+                if !is_power_of_ten(current)
+                    || (next.value != 5 * current && next.value != 10 * current)
+                    || (is_ligature(c) && next.pos.char != pos.char)
+                    || !self.notation.can_subtract_from(next.value)
+                {
+                    return Err(RomanNumeralError::IllegalSubtractivePair(c, next.c, pos));
+                }
+                (next.value - current, Token::Subtractive)
+            }
+            _ if is_power_of_ten(current) => (current, Token::Unit),
+            _ => (current, Token::Five),
+        };
+
+        let token_place = decimal_place(current);
+        if token_place > self.place {
+            return Err(RomanNumeralError::OutOfOrder(c, pos));
+        }
+        if token_place < self.place {
+            self.place = token_place;
+            self.seen_five = false;
+            self.seen_subtractive = false;
+            self.units = 0;
+            self.ligature = None;
+        } else if self.seen_subtractive {
+            // nothing may follow a subtractive pair within the same place, like IXI
+            return Err(RomanNumeralError::OutOfOrder(c, pos));
+        } else if let Some(ligature) = self.ligature {
+            // nor a ligature, like ⅫI
+            if ligature != pos.char {
+                return Err(RomanNumeralError::OutOfOrder(c, pos));
+            }
+        }
+        if is_ligature(c) {
+            self.ligature = Some(pos.char);
+        }
+
+        match token {
+            Token::Subtractive if self.seen_five || self.units > 0 => {
+                return Err(RomanNumeralError::OutOfOrder(c, pos));
+            }
+            Token::Subtractive => self.seen_subtractive = true,
+            Token::Five if self.seen_five => {
+                return Err(RomanNumeralError::RepeatedSymbol(c, pos));
+            }
+            Token::Five if self.units > 0 => return Err(RomanNumeralError::OutOfOrder(c, pos)),
+            Token::Five => self.seen_five = true,
+            Token::Unit => {
+                self.units += 1;
+                if self.units > self.notation.max_repeats(current, self.seen_five) {
+                    return Err(RomanNumeralError::TooManyRepeats(c, pos));
+                }
+            }
+        }
+
+        self.value += value;
+        Ok(matches!(token, Token::Subtractive))
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) const fn parse_strict(
+    symbols: &[Symbol],
+    notation: Notation,
+) -> Result<i64, RomanNumeralError> {
+    if symbols.is_empty() {
+        return Err(RomanNumeralError::EmptyInput);
+    }
+
+    let mut parser = StrictParser::new(notation);
+    let mut i = 0;
+    while i < symbols.len() {
+        let next = if i + 1 < symbols.len() {
+            Some(symbols[i + 1])
+        } else {
+            None
+        };
+        match parser.step(symbols[i], next) {
+            Ok(true) => i += 2,
+            Ok(false) => i += 1,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(parser.value)
+}
+
+// the same as `parse_strict(&plain_symbols(s)?, Notation::Standard)`, reading the symbols as
+// it goes instead of collecting them first
+pub(crate) const fn parse_plain_strict(s: &str) -> Result<i64, RomanNumeralError> {
+    let mut symbols = PlainSymbols::new(s);
+    let mut current = match symbols.next_symbol() {
+        Some(Ok(symbol)) => symbol,
+        Some(Err(e)) => return Err(e),
+        None => return Err(RomanNumeralError::EmptyInput),
+    };
+
+    let mut parser = StrictParser::new(Notation::Standard);
+    loop {
+        let next = match symbols.next_symbol() {
+            Some(Ok(symbol)) => Some(symbol),
+            Some(Err(e)) => return Err(e),
+            None => None,
+        };
+        let used_next = match parser.step(current, next) {
+            Ok(used_next) => used_next,
+            Err(e) => return Err(e),
+        };
+
+        let following = match next {
+            Some(_) if used_next => symbols.next_symbol(),
+            Some(symbol) => Some(Ok(symbol)),
+            None => None,
+        };
+        current = match following {
+            Some(Ok(symbol)) => symbol,
+            Some(Err(e)) => return Err(e),
+            None => return Ok(parser.value),
+        };
+    }
+}
@@ -0,0 +1,216 @@
+use core::{
+    iter::FusedIterator,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{RomanNumeral, RomanNumeralError};
+
+/// The numerals in a range of values, see [`RomanNumeral::range`].
+///
+/// Steps can be taken from either end, so the range can be reversed with
+/// [`Iterator::rev`], and [`Iterator::step_by`] skips ahead without visiting every
+/// numeral in between.
+#[derive(Debug, Clone)]
+pub struct NumeralRange {
+    // the values left to yield are front..back, kept as u64 so the back can be one past
+    // u32::MAX
+    front: u64,
+    back: u64,
+}
+
+impl NumeralRange {
+    // the numeral for a value known to be in the range
+    fn numeral(v: u64) -> RomanNumeral {
+        RomanNumeral::try_from(v).expect("the range only holds numeral values")
+    }
+}
+
+impl Iterator for NumeralRange {
+    type Item = RomanNumeral;
+
+    fn next(&mut self) -> Option<RomanNumeral> {
+        self.nth(0)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<RomanNumeral> {
+        let v = self.front.saturating_add(n as u64);
+        if v >= self.back {
+            self.front = self.back;
+            return None;
+        }
+        self.front = v + 1;
+        Some(NumeralRange::numeral(v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+
+    fn last(mut self) -> Option<RomanNumeral> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for NumeralRange {
+    fn next_back(&mut self) -> Option<RomanNumeral> {
+        self.nth_back(0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<RomanNumeral> {
+        match self.back.checked_sub(n as u64 + 1) {
+            Some(v) if v >= self.front => {
+                self.back = v;
+                Some(NumeralRange::numeral(v))
+            }
+            _ => {
+                self.back = self.front;
+                None
+            }
+        }
+    }
+}
+
+impl ExactSizeIterator for NumeralRange {}
+
+impl FusedIterator for NumeralRange {}
+
+impl RomanNumeral {
+    /// The numerals with the values in `range`, in increasing order. 0 has no numeral, so a
+    /// range starting there starts at I instead.
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let pages: Vec<_> = RomanNumeral::range(1..=4).map(|n| format!("{n:#}")).collect();
+    /// assert_eq!(pages, ["i", "ii", "iii", "iv"]);
+    ///
+    /// let odd: Vec<_> = RomanNumeral::range(..10).rev().step_by(2).map(|n| n.to_string()).collect();
+    /// assert_eq!(odd, ["IX", "VII", "V", "III", "I"]);
+    /// ```
+    pub fn range(range: impl RangeBounds<u32>) -> NumeralRange {
+        let front = match range.start_bound() {
+            Bound::Included(&start) => u64::from(start),
+            Bound::Excluded(&start) => u64::from(start) + 1,
+            Bound::Unbounded => 1,
+        };
+        let back = match range.end_bound() {
+            Bound::Included(&end) => u64::from(end) + 1,
+            Bound::Excluded(&end) => u64::from(end),
+            Bound::Unbounded => u64::from(u32::MAX) + 1,
+        };
+        let front = front.max(1);
+        NumeralRange {
+            front,
+            back: back.max(front),
+        }
+    }
+
+    /// The next numeral up, failing with [`RomanNumeralError::Overflow`] for
+    /// [`RomanNumeral::MAX`].
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let n = RomanNumeral::with_value(39)?;
+    /// assert_eq!(n.succ()?.to_string(), "XL");
+    /// assert!(RomanNumeral::MAX.succ().is_err());
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub fn succ(self) -> Result<RomanNumeral, RomanNumeralError> {
+        self.checked_add(RomanNumeral::MIN)
+    }
+
+    /// The next numeral down, failing with [`RomanNumeralError::NonPositiveNumeral`] for
+    /// [`RomanNumeral::MIN`].
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let n = RomanNumeral::with_value(40)?;
+    /// assert_eq!(n.pred()?.to_string(), "XXXIX");
+    /// assert!(RomanNumeral::MIN.pred().is_err());
+    /// # Ok::<(), roman_numerals::RomanNumeralError>(())
+    /// ```
+    pub fn pred(self) -> Result<RomanNumeral, RomanNumeralError> {
+        self.checked_sub(RomanNumeral::MIN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use crate::{RomanNumeral, RomanNumeralError};
+
+    fn values(range: impl Iterator<Item = RomanNumeral>) -> Vec<u32> {
+        range.map(|n| n.to_int()).collect()
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(values(RomanNumeral::range(3..6)), [3, 4, 5]);
+        assert_eq!(values(RomanNumeral::range(3..=6)), [3, 4, 5, 6]);
+        assert_eq!(values(RomanNumeral::range(0..3)), [1, 2]);
+        assert_eq!(values(RomanNumeral::range(..=2)), [1, 2]);
+        let (start, end) = (6, 3);
+        assert!(values(RomanNumeral::range(start..end)).is_empty());
+        assert!(values(RomanNumeral::range(0..=0)).is_empty());
+        assert_eq!(
+            values(RomanNumeral::range(u32::MAX - 1..)),
+            [u32::MAX - 1, u32::MAX]
+        );
+        assert_eq!(RomanNumeral::range(..).len(), u32::MAX as usize);
+    }
+
+    #[test]
+    fn stepping_and_reversing() {
+        assert_eq!(
+            values(RomanNumeral::range(1..=10).step_by(3)),
+            [1, 4, 7, 10]
+        );
+        assert_eq!(values(RomanNumeral::range(1..=5).rev()), [5, 4, 3, 2, 1]);
+        assert_eq!(
+            values(RomanNumeral::range(1..=10).rev().step_by(4)),
+            [10, 6, 2]
+        );
+        assert_eq!(
+            values(RomanNumeral::range(..).step_by(1 << 31)),
+            [1, (1 << 31) + 1]
+        );
+
+        let mut range = RomanNumeral::range(1..=4);
+        assert_eq!(range.next().unwrap().to_int(), 1);
+        assert_eq!(range.next_back().unwrap().to_int(), 4);
+        assert_eq!(range.len(), 2);
+        assert_eq!(values(range), [2, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn matches_converting_each_integer() {
+        for (i, n) in (1..2_000).zip(RomanNumeral::range(1..2_000)) {
+            assert_eq!(
+                n.to_roman_numeral_string(),
+                RomanNumeral::with_value(i)
+                    .unwrap()
+                    .to_roman_numeral_string()
+            );
+        }
+    }
+
+    #[test]
+    fn successor_and_predecessor() {
+        let n = RomanNumeral::with_value(9).unwrap();
+        assert_eq!(n.succ().unwrap().to_int(), 10);
+        assert_eq!(n.pred().unwrap().to_int(), 8);
+        assert_eq!(
+            RomanNumeral::MAX.succ().unwrap_err(),
+            RomanNumeralError::Overflow
+        );
+        assert_eq!(
+            RomanNumeral::MIN.pred().unwrap_err(),
+            RomanNumeralError::NonPositiveNumeral
+        );
+    }
+}
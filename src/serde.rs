@@ -0,0 +1,223 @@
+//! Serde support, enabled with the `serde` feature.
+//!
+//! [`RomanNumeral`] serializes as its canonical numeral and deserializes from either a
+//! numeral or an integer, or only from a numeral in formats that aren't human readable like
+//! postcard and bincode. Use the [`string`] or [`integer`] modules with `#[serde(with)]` to
+//! stick to one representation.
+//!
+//! ```
+//! use roman_numerals::RomanNumeral;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Chapter {
+//!     number: RomanNumeral,
+//!     #[serde(with = "roman_numerals::serde::integer")]
+//!     page: RomanNumeral,
+//! }
+//!
+//! let chapter: Chapter = serde_json::from_str(r#"{ "number": 14, "page": 203 }"#).unwrap();
+//! assert_eq!(
+//!     serde_json::to_string(&chapter).unwrap(),
+//!     r#"{"number":"XIV","page":203}"#
+//! );
+//! ```
+
+use core::{fmt, str::FromStr};
+
+use ::serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::RomanNumeral;
+
+impl Serialize for RomanNumeral {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        string::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RomanNumeral {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<RomanNumeral, D::Error> {
+        // binary formats like postcard can't say which representation comes next, so they
+        // only get the string that serializing writes
+        if !deserializer.is_human_readable() {
+            return string::deserialize(deserializer);
+        }
+        deserializer.deserialize_any(NumeralVisitor {
+            strings: true,
+            integers: true,
+        })
+    }
+}
+
+// reads a numeral from whichever representations are allowed
+struct NumeralVisitor {
+    strings: bool,
+    integers: bool,
+}
+
+impl Visitor<'_> for NumeralVisitor {
+    type Value = RomanNumeral;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.strings, self.integers) {
+            (true, true) => f.write_str("a roman numeral or a positive integer"),
+            (true, false) => f.write_str("a roman numeral"),
+            _ => f.write_str("a positive integer"),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<RomanNumeral, E> {
+        if !self.strings {
+            return Err(E::invalid_type(de::Unexpected::Str(v), &self));
+        }
+        RomanNumeral::from_str(v).map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<RomanNumeral, E> {
+        if !self.integers {
+            return Err(E::invalid_type(de::Unexpected::Unsigned(v), &self));
+        }
+        RomanNumeral::try_from(v).map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<RomanNumeral, E> {
+        if !self.integers {
+            return Err(E::invalid_type(de::Unexpected::Signed(v), &self));
+        }
+        RomanNumeral::try_from(v).map_err(E::custom)
+    }
+}
+
+/// Serializes a [`RomanNumeral`] as its canonical numeral and only accepts numerals when
+/// deserializing. This is what [`RomanNumeral`]'s own impls do, apart from also accepting
+/// integers.
+pub mod string {
+    use super::*;
+
+    /// Serializes `numeral` as a string like "MCMLXXXIV".
+    pub fn serialize<S: Serializer>(
+        numeral: &RomanNumeral,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(numeral)
+    }
+
+    /// Deserializes a numeral from a string, read like [`FromStr`] does.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RomanNumeral, D::Error> {
+        deserializer.deserialize_str(NumeralVisitor {
+            strings: true,
+            integers: false,
+        })
+    }
+}
+
+/// Serializes a [`RomanNumeral`] as its value and only accepts integers when deserializing.
+pub mod integer {
+    use super::*;
+
+    /// Serializes `numeral` as an integer like 1984.
+    pub fn serialize<S: Serializer>(
+        numeral: &RomanNumeral,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(numeral.to_int())
+    }
+
+    /// Deserializes a numeral from a positive integer.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RomanNumeral, D::Error> {
+        deserializer.deserialize_u32(NumeralVisitor {
+            strings: false,
+            integers: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::RomanNumeral;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Outline {
+        either: RomanNumeral,
+        #[serde(with = "crate::serde::string")]
+        string: RomanNumeral,
+        #[serde(with = "crate::serde::integer")]
+        integer: RomanNumeral,
+    }
+
+    fn n(v: u32) -> RomanNumeral {
+        RomanNumeral::with_value(v).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let outline = Outline {
+            either: n(4),
+            string: n(9),
+            integer: n(40),
+        };
+        let json = serde_json::to_string(&outline).unwrap();
+        assert_eq!(json, r#"{"either":"IV","string":"IX","integer":40}"#);
+        assert_eq!(serde_json::from_str::<Outline>(&json).unwrap(), outline);
+    }
+
+    #[test]
+    fn round_trip_without_describing_types() {
+        let outline = Outline {
+            either: n(1984),
+            string: n(9),
+            integer: n(40),
+        };
+        let bytes = postcard::to_allocvec(&outline).unwrap();
+        assert_eq!(postcard::from_bytes::<Outline>(&bytes).unwrap(), outline);
+        let numeral = postcard::to_allocvec(&n(14)).unwrap();
+        assert_eq!(
+            postcard::from_bytes::<RomanNumeral>(&numeral).unwrap(),
+            n(14)
+        );
+    }
+
+    #[test]
+    fn accepts_either_by_default() {
+        let from_str: RomanNumeral = serde_json::from_str(r#""xiv""#).unwrap();
+        let from_int: RomanNumeral = serde_json::from_str("14").unwrap();
+        assert_eq!(from_str, from_int);
+    }
+
+    #[test]
+    fn errors() {
+        let err = serde_json::from_str::<RomanNumeral>(r#""MCMC""#).unwrap_err();
+        assert!(err.to_string().contains("'C' is out of order"), "{err}");
+        let err = serde_json::from_str::<RomanNumeral>("0").unwrap_err();
+        assert!(err.to_string().contains("positive"), "{err}");
+        let err = serde_json::from_str::<RomanNumeral>("-4").unwrap_err();
+        assert!(err.to_string().contains("positive"), "{err}");
+        let err = serde_json::from_str::<RomanNumeral>("5000000000").unwrap_err();
+        assert!(err.to_string().contains("too large"), "{err}");
+
+        let err =
+            serde_json::from_str::<Outline>(r#"{"either":1,"string":2,"integer":3}"#).unwrap_err();
+        assert!(
+            err.to_string().contains("expected a roman numeral"),
+            "{err}"
+        );
+        let err =
+            serde_json::from_str::<Outline>(r#"{"either":"I","string":"II","integer":"III"}"#)
+                .unwrap_err();
+        assert!(
+            err.to_string().contains("expected a positive integer"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,327 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::{fmt::Write, ops::Range, str::FromStr};
+
+use crate::FormatOptions;
+#[cfg(feature = "alloc")]
+use crate::RomanNumeral;
+
+/// Which way [`convert_text`] converts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextDirection {
+    /// Numerals standing on their own as words become integers, like "Chapter XIV" to
+    /// "Chapter 14".
+    #[default]
+    ToIntegers,
+    /// Integers in square brackets become numerals, like "Chapter \[14\]" to "Chapter XIV".
+    ToNumerals,
+}
+
+/// What [`convert_text`] takes to be a numeral, and how it writes them.
+///
+/// A word is only read as a numeral if it is written all in capitals (or all in lowercase,
+/// with [`TextOptions::lowercase`]) and follows the strict rules of
+/// [`FromStr`](core::str::FromStr). Words that pass are still skipped if they're shorter
+/// than [`TextOptions::min_len`] or on the [`TextOptions::ignore`] list, which keeps the
+/// pronoun "I" and words like "MIX" as they are, unless they come right after one of the
+/// [`TextOptions::context`] words, as in "Chapter I" or "Louis XIV".
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use roman_numerals::{convert_text, TextOptions};
+///
+/// const PAGES: TextOptions = TextOptions::new().lowercase(true).context(&["page", "p."]);
+///
+/// assert_eq!(convert_text("see p. iv and I mean it", PAGES), "see p. 4 and I mean it");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextOptions<'a> {
+    direction: TextDirection,
+    min_len: usize,
+    lowercase: bool,
+    context: &'a [&'a str],
+    ignore: &'a [&'a str],
+    format: FormatOptions,
+}
+
+impl<'a> TextOptions<'a> {
+    /// The words after which a numeral is always read, whatever its length. Abbreviations
+    /// include their full stop.
+    pub const DEFAULT_CONTEXT: &'static [&'static str] = &[
+        "chapter",
+        "ch.",
+        "part",
+        "book",
+        "volume",
+        "vol.",
+        "act",
+        "scene",
+        "section",
+        "appendix",
+        "canto",
+        "psalm",
+        "plate",
+        "fig.",
+        "no.",
+        "louis",
+        "henry",
+        "george",
+        "edward",
+        "charles",
+        "richard",
+        "elizabeth",
+        "pope",
+        "pius",
+        "war",
+    ];
+
+    /// Words that are valid numerals but more often mean something else.
+    pub const DEFAULT_IGNORE: &'static [&'static str] = &[
+        "MIX", "DIX", "LIV", "MI", "DI", "LI", "CD", "DC", "CV", "MC", "MD", "CC", "XL", "XXL",
+        "XXX",
+    ];
+
+    /// Converts numerals to integers, with a minimum length of 2, the default context and
+    /// ignore lists, and numerals written like [`FormatOptions::new`].
+    pub const fn new() -> TextOptions<'static> {
+        TextOptions {
+            direction: TextDirection::ToIntegers,
+            min_len: 2,
+            lowercase: false,
+            context: TextOptions::DEFAULT_CONTEXT,
+            ignore: TextOptions::DEFAULT_IGNORE,
+            format: FormatOptions::new(),
+        }
+    }
+
+    /// Which way to convert.
+    pub const fn direction(mut self, direction: TextDirection) -> TextOptions<'a> {
+        self.direction = direction;
+        self
+    }
+
+    /// The fewest letters a numeral needs when it doesn't follow a context word.
+    pub const fn min_len(mut self, min_len: usize) -> TextOptions<'a> {
+        self.min_len = min_len;
+        self
+    }
+
+    /// Also read numerals written all in lowercase, like "xiv".
+    pub const fn lowercase(mut self, lowercase: bool) -> TextOptions<'a> {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// The words after which a numeral is always read, compared ignoring ASCII case and
+    /// replacing [`TextOptions::DEFAULT_CONTEXT`].
+    pub const fn context(mut self, context: &'a [&'a str]) -> TextOptions<'a> {
+        self.context = context;
+        self
+    }
+
+    /// Words never read as numerals unless they follow a context word, compared ignoring
+    /// ASCII case and replacing [`TextOptions::DEFAULT_IGNORE`].
+    pub const fn ignore(mut self, ignore: &'a [&'a str]) -> TextOptions<'a> {
+        self.ignore = ignore;
+        self
+    }
+
+    /// How to write the numerals for [`TextDirection::ToNumerals`].
+    pub const fn format(mut self, format: FormatOptions) -> TextOptions<'a> {
+        self.format = format;
+        self
+    }
+}
+
+impl Default for TextOptions<'_> {
+    fn default() -> Self {
+        TextOptions::new()
+    }
+}
+
+// letters and digits, along with apostrophes and hyphens between them so "I'm" and
+// "X-ray" are single words
+#[cfg(feature = "alloc")]
+fn words(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut pos = 0;
+    core::iter::from_fn(move || {
+        let start = pos + text[pos..].find(char::is_alphanumeric)?;
+        let mut chars = text[start..].char_indices().peekable();
+        let mut end = text.len();
+        while let Some((i, c)) = chars.next() {
+            let joined = matches!(c, '\'' | '\u{2019}' | '-')
+                && chars.peek().is_some_and(|&(_, c)| c.is_alphanumeric());
+            if !c.is_alphanumeric() && !joined {
+                end = start + i;
+                break;
+            }
+        }
+        pos = end;
+        Some(start..end)
+    })
+}
+
+// whether only whitespace separates `start` from a context word ending at `prev.end`, or
+// a context abbreviation ending in a full stop just after it
+#[cfg(feature = "alloc")]
+fn follows_context(text: &str, prev: Range<usize>, start: usize, context: &[&str]) -> bool {
+    let between = &text[prev.end..start];
+    let (end, gap) = match between.strip_prefix('.') {
+        Some(gap) => (prev.end + 1, gap),
+        None => (prev.end, between),
+    };
+    let word = &text[prev.start..end];
+    !gap.is_empty()
+        && gap.chars().all(char::is_whitespace)
+        && context.iter().any(|c| c.eq_ignore_ascii_case(word))
+}
+
+// the numeral `word` stands for, if the options take it to be one
+#[cfg(feature = "alloc")]
+fn numeral_in(
+    text: &str,
+    prev: Option<Range<usize>>,
+    word: Range<usize>,
+    options: TextOptions,
+) -> Option<RomanNumeral> {
+    let s = &text[word.clone()];
+    let upper = s.bytes().all(|b| b.is_ascii_uppercase());
+    let lower = s.bytes().all(|b| b.is_ascii_lowercase());
+    if !(upper || options.lowercase && lower) {
+        return None;
+    }
+    let numeral = RomanNumeral::from_str(s).ok()?;
+    if prev.is_some_and(|prev| follows_context(text, prev, word.start, options.context)) {
+        return Some(numeral);
+    }
+    let ignored = options.ignore.iter().any(|w| w.eq_ignore_ascii_case(s));
+    (s.len() >= options.min_len && !ignored).then_some(numeral)
+}
+
+// the numeral for an integer in square brackets starting at `start`, and where it ends
+#[cfg(feature = "alloc")]
+fn bracketed_at(text: &str, start: usize) -> Option<(RomanNumeral, usize)> {
+    let rest = text[start..].strip_prefix('[')?;
+    let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+    if digits == 0 || !rest[digits..].starts_with(']') {
+        return None;
+    }
+    let numeral = RomanNumeral::with_value(rest[..digits].parse().ok()?).ok()?;
+    Some((numeral, start + digits + 2))
+}
+
+/// Rewrites the numerals in `text` as integers, or the integers in square brackets as
+/// numerals, leaving everything else exactly as it was. See [`TextOptions`] for which
+/// words count as numerals.
+///
+/// ```
+/// use roman_numerals::{convert_text, TextDirection, TextOptions};
+///
+/// let text = "In Chapter I, Louis XIV did not MIX the paint. I think MCMLXXXIV was fine.";
+/// assert_eq!(
+///     convert_text(text, TextOptions::new()),
+///     "In Chapter 1, Louis 14 did not MIX the paint. I think 1984 was fine."
+/// );
+///
+/// let options = TextOptions::new().direction(TextDirection::ToNumerals);
+/// assert_eq!(convert_text("Super Bowl [50], [0] and [x]", options), "Super Bowl L, [0] and [x]");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn convert_text(text: &str, options: TextOptions) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;
+    match options.direction {
+        TextDirection::ToIntegers => {
+            let mut prev = None;
+            for word in words(text) {
+                if let Some(numeral) = numeral_in(text, prev.clone(), word.clone(), options) {
+                    result.push_str(&text[copied..word.start]);
+                    write!(result, "{}", numeral.to_int())
+                        .expect("writing to a String never fails");
+                    copied = word.end;
+                }
+                prev = Some(word);
+            }
+        }
+        TextDirection::ToNumerals => {
+            for (start, _) in text.match_indices('[') {
+                let Some((numeral, end)) = bracketed_at(text, start) else {
+                    continue;
+                };
+                result.push_str(&text[copied..start]);
+                numeral
+                    .write_roman_with(&mut result, options.format)
+                    .expect("writing to a String never fails");
+                copied = end;
+            }
+        }
+    }
+    result.push_str(&text[copied..]);
+    result
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use std::string::String;
+
+    use crate::{convert_text, FormatOptions, TextDirection, TextOptions};
+
+    fn to_integers(text: &str) -> String {
+        convert_text(text, TextOptions::new())
+    }
+
+    #[test]
+    fn skips_words_that_look_like_numerals() {
+        for text in [
+            "I did it",
+            "MIX, DID, CIVIL and LIVID",
+            "I'm an X-ray of XIVth-century VIM",
+            "Mix Did Civil",
+            "xiv in lowercase",
+            "Chapter\u{2163}",
+        ] {
+            assert_eq!(to_integers(text), text);
+        }
+    }
+
+    #[test]
+    fn converts_numerals() {
+        assert_eq!(to_integers("XIV, (MMXXIII)!"), "14, (2023)!");
+        assert_eq!(
+            to_integers("Part I: Vol. II, World War II"),
+            "Part 1: Vol. 2, World War 2"
+        );
+        assert_eq!(
+            to_integers("Pope Pius IX\nChapter\tMIX"),
+            "Pope Pius 9\nChapter\t1009"
+        );
+        assert_eq!(to_integers("Chapter. I went"), "Chapter. I went");
+        assert_eq!(to_integers("naïve XII «ünïcödé»"), "naïve 12 «ünïcödé»");
+    }
+
+    #[test]
+    fn options() {
+        let options = TextOptions::new().min_len(4);
+        assert_eq!(convert_text("XIV and XVII", options), "XIV and 17");
+        let options = TextOptions::new().lowercase(true);
+        assert_eq!(convert_text("xiv and Xiv", options), "14 and Xiv");
+        let options = TextOptions::new().context(&["opus"]).ignore(&[]);
+        assert_eq!(
+            convert_text("Opus I, Chapter I, MIX", options),
+            "Opus 1, Chapter I, 1009"
+        );
+    }
+
+    #[test]
+    fn converts_bracketed_integers() {
+        let options = TextOptions::new().direction(TextDirection::ToNumerals);
+        assert_eq!(
+            convert_text("[[4]] [1984] [12a] [] [-1] [99999999999] XIV [", options),
+            "[IV] MCMLXXXIV [12a] [] [-1] [99999999999] XIV ["
+        );
+        let options = options.format(FormatOptions::new().lowercase(true));
+        assert_eq!(convert_text("page [4]", options), "page iv");
+    }
+}
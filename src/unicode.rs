@@ -0,0 +1,211 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt::{self, Display, Write};
+
+use crate::{format::pad, greatest_str_leq_than_n, RomanNumeral};
+
+// Ⅰ, the ligatures up to Ⅻ follow it in order
+const NUMBER_FORMS_ONE: u32 = 0x2160;
+
+// ⅰ to ⅿ are Ⅰ to Ⅿ moved this far along
+const NUMBER_FORMS_LOWERCASE_OFFSET: u32 = 0x10;
+
+// the values of the symbols a character from the Number Forms block stands for, apart from
+// ↅ and ↆ, see `archaic_form_values`
+pub(crate) const fn number_form_values(c: char) -> Option<&'static [i64]> {
+    match c {
+        'Ⅰ' | 'ⅰ' => Some(&[1]),
+        'Ⅱ' | 'ⅱ' => Some(&[1, 1]),
+        'Ⅲ' | 'ⅲ' => Some(&[1, 1, 1]),
+        'Ⅳ' | 'ⅳ' => Some(&[1, 5]),
+        'Ⅴ' | 'ⅴ' => Some(&[5]),
+        'Ⅵ' | 'ⅵ' => Some(&[5, 1]),
+        'Ⅶ' | 'ⅶ' => Some(&[5, 1, 1]),
+        'Ⅷ' | 'ⅷ' => Some(&[5, 1, 1, 1]),
+        'Ⅸ' | 'ⅸ' => Some(&[1, 10]),
+        'Ⅹ' | 'ⅹ' => Some(&[10]),
+        'Ⅺ' | 'ⅺ' => Some(&[10, 1]),
+        'Ⅻ' | 'ⅻ' => Some(&[10, 1, 1]),
+        'Ⅼ' | 'ⅼ' => Some(&[50]),
+        'Ⅽ' | 'ⅽ' => Some(&[100]),
+        'Ⅾ' | 'ⅾ' => Some(&[500]),
+        'Ⅿ' | 'ⅿ' | 'ↀ' => Some(&[1_000]),
+        'ↁ' => Some(&[5_000]),
+        'ↂ' => Some(&[10_000]),
+        'ↇ' => Some(&[50_000]),
+        'ↈ' => Some(&[100_000]),
+        _ => None,
+    }
+}
+
+// the values of the early forms of six and fifty, which only apostrophus numerals are read
+// with
+pub(crate) const fn archaic_form_values(c: char) -> Option<&'static [i64]> {
+    match c {
+        // ↅ is a late form of six
+        'ↅ' => Some(&[5, 1]),
+        // ↆ is an early form of fifty
+        'ↆ' => Some(&[50]),
+        _ => None,
+    }
+}
+
+// whether `c` stands for several symbols, like Ⅻ, which nothing of its own decimal place
+// may follow in a strict numeral
+pub(crate) const fn is_ligature(c: char) -> bool {
+    matches!(number_form_values(c), Some([_, _, ..]))
+        || matches!(archaic_form_values(c), Some([_, _, ..]))
+}
+
+fn number_form_letter(c: char) -> char {
+    match c {
+        'I' => 'Ⅰ',
+        'V' => 'Ⅴ',
+        'X' => 'Ⅹ',
+        'L' => 'Ⅼ',
+        'C' => 'Ⅽ',
+        'D' => 'Ⅾ',
+        'M' => 'Ⅿ',
+        _ => c,
+    }
+}
+
+fn number_form_to_lowercase(c: char) -> char {
+    match c {
+        'Ⅰ'..='Ⅿ' => char::from_u32(c as u32 + NUMBER_FORMS_LOWERCASE_OFFSET).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Displays a [`RomanNumeral`] with characters from the Number Forms block, see
+/// [`RomanNumeral::display_unicode`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayUnicode<'a> {
+    numeral: &'a RomanNumeral,
+}
+
+impl Display for DisplayUnicode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lowercase = f.alternate();
+        pad(f, |w| self.numeral.write_unicode(w, lowercase))
+    }
+}
+
+impl RomanNumeral {
+    /// Something to put in `format!` and friends that writes the numeral like
+    /// [`RomanNumeral::to_unicode_string`], honoring fill, width, alignment and precision.
+    /// `{:#}` writes it with the lowercase forms.
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// let n = RomanNumeral::with_value(12).unwrap();
+    /// assert_eq!(format!("{:>3}", n.display_unicode()), "  Ⅻ");
+    /// assert_eq!(format!("{:#}", n.display_unicode()), "ⅻ");
+    /// ```
+    pub fn display_unicode(&self) -> DisplayUnicode<'_> {
+        DisplayUnicode { numeral: self }
+    }
+
+    /// Writes the numeral with characters from the Number Forms block, using the single
+    /// character forms like Ⅻ for the last one to twelve, as on clock faces and in lists.
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// assert_eq!(RomanNumeral::with_value(12).unwrap().to_unicode_string(), "Ⅻ");
+    /// assert_eq!(RomanNumeral::with_value(2024).unwrap().to_unicode_string(), "ⅯⅯⅩⅩⅣ");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_unicode_string(&self) -> String {
+        let mut result = String::new();
+        self.write_unicode(&mut result, false)
+            .expect("writing to a String never fails");
+        result
+    }
+
+    // writes what `to_unicode_string` returns, in the lowercase forms if asked to
+    fn write_unicode(&self, w: &mut dyn Write, lowercase: bool) -> fmt::Result {
+        let case = |c| {
+            if lowercase {
+                number_form_to_lowercase(c)
+            } else {
+                c
+            }
+        };
+
+        // there are ligatures for everything up to twelve, past that only for the ones
+        let tail = match self.value() % 100 {
+            tail @ 1..=12 => tail,
+            rest => rest % 10,
+        };
+        let mut val = self.value() - tail;
+        while val > 0 {
+            let (s, v) = greatest_str_leq_than_n(val);
+            val -= v;
+            for c in s.chars() {
+                w.write_char(case(number_form_letter(c)))?;
+            }
+        }
+
+        if tail > 0 {
+            let ligature =
+                char::from_u32(NUMBER_FORMS_ONE + tail as u32 - 1).expect("Ⅰ to Ⅻ are contiguous");
+            w.write_char(case(ligature))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{ParseMode, RomanNumeral};
+
+    #[test]
+    fn number_forms() {
+        assert_eq!(RomanNumeral::from_str("Ⅻ").unwrap().to_int(), 12);
+        assert_eq!(RomanNumeral::from_str("ⅯⅯⅩⅩⅣ").unwrap().to_int(), 2024);
+        assert_eq!(RomanNumeral::from_str("ⅹⅳ").unwrap().to_int(), 14);
+        assert_eq!(RomanNumeral::from_str("MCMⅬⅩⅩⅩⅣ").unwrap().to_int(), 1984);
+        assert_eq!(RomanNumeral::from_str("ⅩⅫ").unwrap().to_int(), 22);
+        assert_eq!(
+            RomanNumeral::parse("ⅢⅡ", ParseMode::Lenient)
+                .unwrap()
+                .to_int(),
+            5
+        );
+        assert_eq!(RomanNumeral::from_str("ↁ").unwrap().to_int(), 5_000);
+        assert_eq!(RomanNumeral::from_str("ↂM").unwrap().to_int(), 11_000);
+        assert_eq!(RomanNumeral::from_str("ↈↇↀ").unwrap().to_int(), 151_000);
+        assert_eq!(
+            RomanNumeral::parse("ↀ", ParseMode::Lenient)
+                .unwrap()
+                .to_int(),
+            1_000
+        );
+        for s in [
+            "ⅣⅠ", "ⅫⅫ", "ⅨⅩ", "Ↄ", "ⅫI", "ⅡI", "ⅥⅠ", "ⅪV", "ↀↁ", "Ⅻↅ", "ↆ",
+        ] {
+            assert!(
+                RomanNumeral::from_str(s).is_err(),
+                "expected {s:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn convert_back_and_forth() {
+        for i in 1..10_000 {
+            let string = RomanNumeral::with_value(i).unwrap().to_unicode_string();
+            assert_eq!(
+                i,
+                RomanNumeral::from_str(&string).unwrap().to_int(),
+                "result of to_unicode_string(): {:?}",
+                string,
+            );
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::{
+    parse::{char_to_value, parse_strict, positioned_chars, Notation, Symbol},
+    FormatOptions, Position, RomanNumeral, RomanNumeralError,
+};
+
+const COMBINING_OVERLINE: char = '\u{305}';
+const COMBINING_DOUBLE_OVERLINE: char = '\u{33f}';
+
+// what the symbols are multiplied by at each level of marking
+const MULTIPLIERS: [i64; 3] = [1, 1_000, 100_000];
+
+// how large a group may be when there is a more heavily marked group before it
+const GROUP_LIMITS: [i64; 3] = [1_000, 100, i64::MAX];
+
+/// How [`RomanNumeral::to_vinculum_string`](crate::RomanNumeral::to_vinculum_string) marks multiplied symbols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VinculumStyle {
+    /// A combining overline (U+0305) on each symbol for ×1000 and a combining double
+    /// overline (U+033F) for ×100000, like "V̅" for 5000.
+    #[default]
+    Unicode,
+    /// One underscore before each symbol for ×1000 and two for ×100000, like "_V" for 5000.
+    Underscore,
+    /// The group in parentheses for ×1000 and in double parentheses for ×100000, like "(V)"
+    /// for 5000.
+    Parentheses,
+}
+
+// passes every char on with the marks for its level around it
+struct Marked<'a> {
+    inner: &'a mut dyn fmt::Write,
+    style: VinculumStyle,
+    level: usize,
+}
+
+impl fmt::Write for Marked<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match self.style {
+                VinculumStyle::Unicode => {
+                    self.inner.write_char(c)?;
+                    self.inner.write_char(if self.level == 1 {
+                        COMBINING_OVERLINE
+                    } else {
+                        COMBINING_DOUBLE_OVERLINE
+                    })?;
+                }
+                VinculumStyle::Underscore => {
+                    for _ in 0..self.level {
+                        self.inner.write_char('_')?;
+                    }
+                    self.inner.write_char(c)?;
+                }
+                VinculumStyle::Parentheses => self.inner.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// writes `value` as marked groups, `write_group` writes the plain numeral for each group
+// given its value and level of marking
+pub(crate) fn write_vinculum(
+    w: &mut dyn fmt::Write,
+    value: i64,
+    style: VinculumStyle,
+    write_group: impl Fn(&mut dyn fmt::Write, i64, usize) -> fmt::Result,
+) -> fmt::Result {
+    let groups = [
+        (2, value / MULTIPLIERS[2]),
+        (1, value / MULTIPLIERS[1] % GROUP_LIMITS[1]),
+        (0, value % GROUP_LIMITS[0]),
+    ];
+    for (level, group) in groups {
+        if level == 0 {
+            write_group(w, group, level)?;
+            continue;
+        }
+        if group == 0 {
+            continue;
+        }
+
+        let parens = style == VinculumStyle::Parentheses;
+        if parens {
+            for _ in 0..level {
+                w.write_char('(')?;
+            }
+        }
+        write_group(
+            &mut Marked {
+                inner: w,
+                style,
+                level,
+            },
+            group,
+            level,
+        )?;
+        if parens {
+            for _ in 0..level {
+                w.write_char(')')?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// the marks in a numeral all have to be in the same style, the first one sets it
+#[cfg(feature = "alloc")]
+fn check_style(
+    style: &mut Option<VinculumStyle>,
+    marks: VinculumStyle,
+    c: char,
+    pos: Position,
+) -> Result<(), RomanNumeralError> {
+    match *style {
+        Some(style) if style != marks => Err(RomanNumeralError::InvalidChar(c, pos)),
+        _ => {
+            *style = Some(marks);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl RomanNumeral {
+    /// Writes the numeral in vinculum notation. Below 4000 this is the same as
+    /// [`RomanNumeral::to_roman_numeral_string`], above it the hundred thousands and the
+    /// thousands are written as marked groups in front of the rest.
+    ///
+    /// ```
+    /// use roman_numerals::{RomanNumeral, VinculumStyle};
+    ///
+    /// let n = RomanNumeral::with_value(1_234_567).unwrap();
+    /// assert_eq!(n.to_vinculum_string(VinculumStyle::Underscore), "__X__I__I_X_X_X_I_VDLXVII");
+    /// assert_eq!(n.to_vinculum_string(VinculumStyle::Parentheses), "((XII))(XXXIV)DLXVII");
+    /// ```
+    pub fn to_vinculum_string(&self, style: VinculumStyle) -> String {
+        self.to_roman_numeral_string_with(FormatOptions::new().vinculum(Some(style)))
+    }
+
+    /// Reads a numeral in any one of the [`VinculumStyle`]s, spelled the way
+    /// [`RomanNumeral::to_vinculum_string`] writes it. Each group of equally marked symbols
+    /// has to be canonical and fit below the more heavily marked group before it, the
+    /// thousands only have a group of their own from 4000 on, the marks can't be mixed or
+    /// doubled up, and only the letters are read, so neither "(MMMM)" nor "(_V)" nor
+    /// "V\u{305}\u{305}" nor "Ⅻ" is.
+    ///
+    /// ```
+    /// use roman_numerals::RomanNumeral;
+    ///
+    /// assert_eq!(RomanNumeral::from_vinculum_str("X\u{305}CIX").unwrap().to_int(), 10_109);
+    /// assert_eq!(RomanNumeral::from_vinculum_str("(IV)CD").unwrap().to_int(), 4_400);
+    /// ```
+    pub fn from_vinculum_str(s: &str) -> Result<RomanNumeral, RomanNumeralError> {
+        // every symbol along with how many levels of marks apply to it
+        let mut symbols: Vec<(usize, _, _)> = Vec::new();
+        let mut underscores = Vec::new();
+        let mut open_parens = Vec::new();
+        // the parentheses opened since the last symbol
+        let mut opened = 0;
+        let mut style = None;
+
+        let mut chars = positioned_chars(s).peekable();
+        while let Some((pos, c)) = chars.next() {
+            match c {
+                '_' => {
+                    check_style(&mut style, VinculumStyle::Underscore, c, pos)?;
+                    underscores.push((pos, c));
+                }
+                '(' => {
+                    check_style(&mut style, VinculumStyle::Parentheses, c, pos)?;
+                    open_parens.push((pos, c));
+                    opened += 1;
+                }
+                ')' => {
+                    if let Some(&(pos, c)) = underscores.first() {
+                        return Err(RomanNumeralError::InvalidChar(c, pos));
+                    }
+                    open_parens
+                        .pop()
+                        .ok_or(RomanNumeralError::InvalidChar(c, pos))?;
+                }
+                COMBINING_OVERLINE | COMBINING_DOUBLE_OVERLINE => {
+                    return Err(RomanNumeralError::InvalidChar(c, pos));
+                }
+                _ => {
+                    let mut level = underscores.len() + open_parens.len();
+                    underscores.clear();
+                    if let Some(&(pos, mark @ (COMBINING_OVERLINE | COMBINING_DOUBLE_OVERLINE))) =
+                        chars.peek()
+                    {
+                        check_style(&mut style, VinculumStyle::Unicode, mark, pos)?;
+                        level += if mark == COMBINING_OVERLINE { 1 } else { 2 };
+                        chars.next();
+                    }
+                    // one overline or double overline per symbol, V\u{305}\u{305} isn't written
+                    if let Some(&(pos, mark @ (COMBINING_OVERLINE | COMBINING_DOUBLE_OVERLINE))) =
+                        chars.peek()
+                    {
+                        return Err(RomanNumeralError::InvalidChar(mark, pos));
+                    }
+                    if level >= MULTIPLIERS.len() {
+                        return Err(RomanNumeralError::InvalidChar(c, pos));
+                    }
+                    // each group sits in parentheses of its own, opened right before it
+                    if style == Some(VinculumStyle::Parentheses) {
+                        let starts_group = symbols.last().map(|&(last, ..)| last) != Some(level);
+                        let expected = if starts_group { level } else { 0 };
+                        if opened != expected {
+                            return Err(RomanNumeralError::OutOfOrder(c, pos));
+                        }
+                    }
+                    opened = 0;
+                    symbols.push((level, pos, c));
+                }
+            }
+        }
+        if let Some(&(pos, c)) = underscores.first().or(open_parens.first()) {
+            return Err(RomanNumeralError::InvalidChar(c, pos));
+        }
+
+        let mut value = 0;
+        let mut previous_level = None;
+        for group in symbols.chunk_by(|a, b| a.0 == b.0) {
+            let level = group[0].0;
+            // only the letters, as there's no marking Ⅻ or ↂ
+            let mut symbols = Vec::new();
+            for &(_, pos, c) in group {
+                let value = char_to_value(c, pos)?;
+                symbols.push(Symbol { pos, c, value });
+            }
+            let Symbol { pos, c, .. } = symbols[0];
+            if previous_level.is_some_and(|previous| level > previous) {
+                return Err(RomanNumeralError::OutOfOrder(c, pos));
+            }
+
+            let group_value = parse_strict(&symbols, Notation::Standard)?;
+            // the thousands only fit below a hundred thousand even on their own, and a
+            // numeral without marks stops at 3999
+            let limit = match (previous_level, level) {
+                (None, 0) => 4_000,
+                _ => GROUP_LIMITS[level],
+            };
+            if group_value >= limit {
+                return Err(RomanNumeralError::OutOfOrder(c, pos));
+            }
+            value += group_value * MULTIPLIERS[level];
+            previous_level = Some(level);
+        }
+
+        match symbols.first() {
+            // anything below 4000 is written without marks
+            Some(&(level, pos, c)) if level > 0 && value < 4_000 => {
+                Err(RomanNumeralError::OutOfOrder(c, pos))
+            }
+            Some(_) => RomanNumeral::from_i64(value),
+            None => Err(RomanNumeralError::EmptyInput),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{Position, RomanNumeral, RomanNumeralError, VinculumStyle};
+
+    const STYLES: [VinculumStyle; 3] = [
+        VinculumStyle::Unicode,
+        VinculumStyle::Underscore,
+        VinculumStyle::Parentheses,
+    ];
+
+    #[test]
+    fn small_numbers_are_plain() {
+        for style in STYLES {
+            let string = RomanNumeral::with_value(3999)
+                .unwrap()
+                .to_vinculum_string(style);
+            assert_eq!(string, "MMMCMXCIX");
+        }
+    }
+
+    #[test]
+    fn unicode_marks() {
+        let string = RomanNumeral::with_value(501_004)
+            .unwrap()
+            .to_vinculum_string(VinculumStyle::Unicode);
+        assert_eq!(string, "V\u{33f}I\u{305}IV");
+    }
+
+    #[test]
+    fn convert_back_and_forth() {
+        let values = (1..20_000)
+            .chain((20_000..2_000_000).step_by(997))
+            .chain([3_999_999_999, 4_294_967_295]);
+        for i in values {
+            for style in STYLES {
+                let string = RomanNumeral::with_value(i)
+                    .unwrap()
+                    .to_vinculum_string(style);
+                assert_eq!(
+                    i,
+                    RomanNumeral::from_vinculum_str(&string).unwrap().to_int(),
+                    "result of to_vinculum_string(): {:?}",
+                    string,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn doubled_overline() {
+        assert_eq!(
+            RomanNumeral::from_vinculum_str("V\u{305}\u{305}").unwrap_err(),
+            RomanNumeralError::InvalidChar('\u{305}', Position { byte: 3, char: 2 })
+        );
+        let r = RomanNumeral::from_vinculum_str("V\u{33f}").unwrap();
+        assert_eq!(r.to_int(), 500_000);
+    }
+
+    #[test]
+    fn malformed_marks() {
+        for s in ["(V", "V)", "V_", "\u{305}V", "___V", "(((V)))", "", "()"] {
+            assert!(
+                RomanNumeral::from_vinculum_str(s).is_err(),
+                "expected {s:?} to be rejected"
+            );
+        }
+        // groups have to get less marked as they go
+        assert_eq!(
+            RomanNumeral::from_vinculum_str("V_V").unwrap_err(),
+            RomanNumeralError::OutOfOrder('V', Position { byte: 2, char: 2 })
+        );
+        // and fit below the group before them
+        assert!(RomanNumeral::from_vinculum_str("_VMMM").is_err());
+        assert!(RomanNumeral::from_vinculum_str("__I_C").is_err());
+        // and be spelled like to_vinculum_string spells them
+        for s in [
+            "(MMMM)",
+            "(_V)",
+            "_V(I)",
+            "V\u{305}_I",
+            "(III)",
+            "I\u{305}V",
+            "M\u{305}",
+            "(I)(V)",
+            "((X)I)",
+            "(X)(I)",
+            "MMMM",
+            "ↂ",
+            "Ⅻ",
+        ] {
+            assert!(
+                RomanNumeral::from_vinculum_str(s).is_err(),
+                "expected {s:?} to be rejected"
+            );
+        }
+    }
+}